@@ -3,8 +3,8 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use astro_stake::msg::{TokenAmountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use astro_stake::state::Pools;
+use astro_stake::msg::{TokenAmountResponse, ExecuteMsg, InstantiateMsg, PoolResponse, PoolsResponse, QueryMsg};
+use astro_stake::state::Config;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -15,6 +15,8 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
-    export_schema(&schema_for!(Pools), &out_dir);
+    export_schema(&schema_for!(Config), &out_dir);
+    export_schema(&schema_for!(PoolResponse), &out_dir);
+    export_schema(&schema_for!(PoolsResponse), &out_dir);
     export_schema(&schema_for!(TokenAmountResponse), &out_dir);
 }
@@ -1,47 +1,73 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{ Uint128, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{ Uint128, coins, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, CosmosMsg, Order, Reply, Response, StdError, StdResult, SubMsg, WasmMsg};
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{TokenAmountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{AstroStakingExecuteMsg, AstroStakingHookMsg, AstroStakingQueryMsg, TokenAmountResponse, ExecuteMsg, InstantiateMsg, PoolResponse, PoolsResponse, QueryMsg, ReceiveMsg};
 
 use cw_controllers::Admin;
-use cw20_base::contract::{execute_mint, query_balance};
-use cw20_base::state::{TOKEN_INFO, TokenInfo, MinterData};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse};
 
-use crate::state::{POOLS_CONTAINER, PoolInfo, TMP_POOLS_CONTAINER};
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+
+use crate::state::{pools, CONFIG, Config, POOL_COUNT, PendingPool, PoolInfo, TMP_POOLS_CONTAINER};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:astro_stake";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const SUPER_ADMIN: &Admin = &Admin::new("SUPER_ADMIN");
-const ASTRO_ADDRESS: &str  = "ibc/EBD5A24C554198EBAF44979C5B4D2C2D312E6EBAB71962C92F735499C7575839";
+const INSTANTIATE_REWARD_POOL_REPLY_ID: u64 = 1;
+const INSTANTIATE_OASTRO_REPLY_ID: u64 = 2;
+// pagination for the pools query
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
     SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
     
-    let token_info = TokenInfo{
-        name: "octaneAstro".to_string(),
-        symbol: "oAstro".to_string(),
-        decimals: 6,
-        total_supply: Uint128::zero(),
-        mint: Some(MinterData { minter: _env.contract.address, cap: None })
+    let config = Config{
+        astro_token: deps.api.addr_validate(&msg.astro_token)?,
+        deposit_denom: msg.deposit_denom,
+        astro_staking: deps.api.addr_validate(&msg.astro_staking)?,
+        xastro_token: deps.api.addr_validate(&msg.xastro_token)?,
+        octane_booster: deps.api.addr_validate(&msg.octane_booster)?,
+        octane_staker: deps.api.addr_validate(&msg.octane_staker)?,
+        reward_pool_code_id: msg.reward_pool_code_id,
+        // set from the reply once the token exists
+        oastro_token: Addr::unchecked(""),
     };
-
-    TOKEN_INFO.save(deps.storage, &token_info)?;
+    CONFIG.save(deps.storage, &config)?;
+    POOL_COUNT.save(deps.storage, &0)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    
+
+    // oAstro lives in its own cw20 contract and this contract is its only minter
+    let token_msg = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: msg.oastro_code_id,
+        msg: to_binary(&oastro_token::msg::InstantiateMsg {
+            name: "octaneAstro".to_string(),
+            symbol: "oAstro".to_string(),
+            decimals: 6,
+            initial_balances: vec![],
+            mint: Some(MinterResponse { minter: env.contract.address.to_string(), cap: None }),
+            marketing: msg.marketing,
+        })?,
+        funds: vec![],
+        label: "oAstro".to_string(),
+    };
 
     Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(token_msg, INSTANTIATE_OASTRO_REPLY_ID))
         .add_attribute("method", "instantiate")
         .add_attribute("owner", msg.admin))
         
@@ -55,48 +81,242 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::XAstroStake {} => stake(deps, info, env),
-        ExecuteMsg::AddPool { lp_token, guage, factory } => add_pool(deps, info, lp_token, guage, factory)
+        ExecuteMsg::XAstroStake {} => stake(deps, info),
+        ExecuteMsg::Receive(msg) => receive(deps, info, msg),
+        ExecuteMsg::AddPool { lp_token, guage, factory } => add_pool(deps, env, info, lp_token, guage, factory),
+        ExecuteMsg::ShutdownPool { pool_id } => shutdown_pool(deps, info, pool_id),
+    }
+}
+
+fn add_pool(mut deps: DepsMut, env: Env, info: MessageInfo, lp_token: String, guage: String, factory: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let pending = PendingPool{
+        lp_token: deps.api.addr_validate(&lp_token)?,
+        guage: deps.api.addr_validate(&guage)?,
+        factory: deps.api.addr_validate(&factory)?,
+    };
 
+    // a shut down pool no longer blocks its LP token, matching the booster
+    if matches!(latest_pool_by_lp_token(deps.as_ref(), &pending.lp_token)?, Some((_, pool)) if pool.active) {
+        return Err(ContractError::PoolExists { lp_token });
     }
+
+    let config = CONFIG.load(deps.storage)?;
+    let pool_id = POOL_COUNT.load(deps.storage)?;
+    TMP_POOLS_CONTAINER.save(deps.storage, &pending)?;
+
+    // the reward pool is administered by this contract so it can be initialised from the reply
+    let instantiate_msg = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: config.reward_pool_code_id,
+        msg: to_binary(&octane_reward_pool::msg::InstantiateMsg { admin: env.contract.address.to_string() })?,
+        funds: vec![],
+        label: format!("octane reward pool {}", pool_id),
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(instantiate_msg, INSTANTIATE_REWARD_POOL_REPLY_ID))
+        .add_attribute("method", "add_pool")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("lp_token", lp_token))
 }
 
-fn add_pool(mut deps: DepsMut, info: MessageInfo, lp_token: String, guage: String, factory: String) -> Result<Response, ContractError>{
+fn shutdown_pool(mut deps: DepsMut, info: MessageInfo, pool_id: u128) -> Result<Response, ContractError>{
     check_admin(deps.branch(), info)?;
 
-    let lp_token_addr = deps.api.addr_validate(&lp_token)?;
-    let pools = POOLS_CONTAINER.load(deps.storage)?;
+    let mut pool = pools().load(deps.storage, pool_id)?;
+    if !pool.active {
+        return Err(ContractError::PoolShutdown { pool_id });
+    }
+    pool.active = false;
+    pools().save(deps.storage, pool_id, &pool)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // the booster pulls the LP and hands over the last harvest before the reward pool freezes
+    let booster_msg = WasmMsg::Execute {
+        contract_addr: config.octane_booster.to_string(),
+        msg: to_binary(&octane_booster::msg::ExecuteMsg::ShutdownPool { pool_id })?,
+        funds: vec![],
+    };
+    let rewards_msg = WasmMsg::Execute {
+        contract_addr: pool.rewards.to_string(),
+        msg: to_binary(&octane_reward_pool::msg::ExecuteMsg::Shutdown {})?,
+        funds: vec![],
+    };
 
-    let found = pools.items.iter().find(|item| item.lp_token == lp_token_addr);
-    
-    let _ = match found {
-        Some(_) => Ok(()),
-        None => TMP_POOLS_CONTAINER.save(deps.storage, lp_token_addr, &true)
+    Ok(Response::new()
+        .add_message(booster_msg)
+        .add_message(rewards_msg)
+        .add_attribute("method", "shutdown_pool")
+        .add_attribute("pool_id", pool_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_REWARD_POOL_REPLY_ID => register_pool(deps, msg),
+        INSTANTIATE_OASTRO_REPLY_ID => register_oastro(deps, msg),
+        id => Err(ContractError::UnknownReply { id }),
+    }
+}
+
+fn register_oastro(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res = parse_reply_instantiate_data(msg)?;
+    let oastro_token = deps.api.addr_validate(&res.contract_address)?;
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.oastro_token = oastro_token.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "register_oastro")
+        .add_attribute("oastro_token", oastro_token))
+}
+
+fn register_pool(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res = parse_reply_instantiate_data(msg)?;
+    let rewards = deps.api.addr_validate(&res.contract_address)?;
+
+    let pending = TMP_POOLS_CONTAINER.load(deps.storage)?;
+    TMP_POOLS_CONTAINER.remove(deps.storage);
+
+    let config = CONFIG.load(deps.storage)?;
+    let pool_id = POOL_COUNT.load(deps.storage)?;
+    let pool = PoolInfo{
+        lp_token: pending.lp_token,
+        guage: pending.guage,
+        rewards,
+        factory: pending.factory,
+        active: true,
+    };
+    pools().save(deps.storage, pool_id, &pool)?;
+    POOL_COUNT.save(deps.storage, &(pool_id + 1))?;
+
+    // this contract only administers the reward pool, its own admin manages the reward schedule
+    let reward_manager = SUPER_ADMIN.get(deps.as_ref())?.ok_or_else(|| StdError::not_found("admin"))?;
+
+    let init_msg = WasmMsg::Execute {
+        contract_addr: pool.rewards.to_string(),
+        msg: to_binary(&octane_reward_pool::msg::ExecuteMsg::Init {
+            astro_token: config.astro_token.to_string(),
+            astro_guage: pool.guage.to_string(),
+            octane_staker: config.octane_staker.to_string(),
+            octane_booster: config.octane_booster.to_string(),
+            lp_token: pool.lp_token.to_string(),
+            oastro_token: config.oastro_token.to_string(),
+            pool_id,
+            reward_manager: reward_manager.to_string(),
+        })?,
+        funds: vec![],
+    };
+    // the booster numbers pools in the same order, since this contract is its pool manager
+    let booster_msg = WasmMsg::Execute {
+        contract_addr: config.octane_booster.to_string(),
+        msg: to_binary(&octane_booster::msg::ExecuteMsg::AddPool {
+            lp_token: pool.lp_token.to_string(),
+            guage: pool.guage.to_string(),
+            rewards: pool.rewards.to_string(),
+        })?,
+        funds: vec![],
     };
-    
 
-    Ok(Response::new())
+    Ok(Response::new()
+        .add_message(init_msg)
+        .add_message(booster_msg)
+        .add_attribute("method", "register_pool")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("rewards", pool.rewards))
 }
 
-fn stake(deps: DepsMut, info: MessageInfo, env: Env) -> Result<Response, ContractError> {
+fn stake(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
     let incoming_astro_amount = info
                         .funds
                         .iter()
-                        .find(|c| c.denom == ASTRO_ADDRESS.to_string())
+                        .find(|c| c.denom == config.deposit_denom)
                         .map(|c| c.amount)
                         .unwrap_or_else(Uint128::zero);
-                        
-    println!("{:#}", incoming_astro_amount);
-    
-    if incoming_astro_amount > Uint128::from(0u128){
-        let sub_info = MessageInfo {
-            sender: env.contract.address.clone(),
-            funds: vec![],
-        };
-        let _res= execute_mint(deps, env, sub_info, info.sender.to_string(), Uint128::from(incoming_astro_amount)).unwrap();
+
+    if incoming_astro_amount.is_zero() {
+        return Err(ContractError::NoDeposit { denom: config.deposit_denom });
+    }
+
+    // the ASTRO is staked right away and the xASTRO goes to the booster, which locks it as vxASTRO
+    let enter_msg = WasmMsg::Execute {
+        contract_addr: config.astro_staking.to_string(),
+        msg: to_binary(&AstroStakingExecuteMsg::Enter { receiver: Some(config.octane_booster.to_string()) })?,
+        funds: coins(incoming_astro_amount.u128(), &config.deposit_denom),
+    };
+
+    Ok(Response::new()
+        .add_message(enter_msg)
+        .add_message(mint_oastro(&config, &info.sender, incoming_astro_amount)?)
+        .add_attribute("method", "xastro staked")
+        .add_attribute("amount", incoming_astro_amount))
+}
+
+fn receive(deps: DepsMut, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    match msg {
+        ReceiveMsg::Deposit { recipient } => {
+            let recipient = deps.api.addr_validate(&recipient.unwrap_or(wrapper.sender))?;
+            // info.sender is the cw20 contract that forwarded the hook
+            deposit_cw20(deps, info.sender, recipient, wrapper.amount)
+        }
     }
+}
+
+fn deposit_cw20(deps: DepsMut, token: Addr, recipient: Addr, amount: Uint128) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if amount.is_zero() {
+        return Err(ContractError::NoDeposit { denom: token.into() });
+    }
+
+    let (forward_msg, oastro_amount) = if token == config.astro_token {
+        let msg = Cw20ExecuteMsg::Send {
+            contract: config.astro_staking.to_string(),
+            amount,
+            msg: to_binary(&AstroStakingHookMsg::Enter { receiver: Some(config.octane_booster.to_string()) })?,
+        };
+        (msg, amount)
+    } else if token == config.xastro_token {
+        // xASTRO is already staked, credit it at the ASTRO it can be redeemed for
+        let total_deposit: Uint128 = deps.querier.query_wasm_smart(&config.astro_staking, &AstroStakingQueryMsg::TotalDeposit {})?;
+        let total_shares: Uint128 = deps.querier.query_wasm_smart(&config.astro_staking, &AstroStakingQueryMsg::TotalShares {})?;
+        let msg = Cw20ExecuteMsg::Transfer {
+            recipient: config.octane_booster.to_string(),
+            amount,
+        };
+        let oastro_amount = amount
+            .checked_multiply_ratio(total_deposit, total_shares)
+            .map_err(|_| ContractError::NoXAstroShares { token: token.to_string() })?;
+        (msg, oastro_amount)
+    } else {
+        return Err(ContractError::UnsupportedToken { token: token.into() });
+    };
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&forward_msg)?,
+            funds: vec![],
+        })
+        .add_message(mint_oastro(&config, &recipient, oastro_amount)?)
+        .add_attribute("method", "deposit")
+        .add_attribute("token", token)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", oastro_amount))
+}
 
-    Ok(Response::new().add_attribute("method", "xastro staked"))
+/// Mints oAstro through the token contract, which only accepts mints from this contract
+fn mint_oastro(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.oastro_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: recipient.to_string(), amount })?,
+        funds: vec![],
+    }.into())
 }
 
 fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
@@ -104,7 +324,7 @@ fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
     
     match super_admin {
         Ok(()) => Ok(()),
-        Err(_not_admin) => return Err(ContractError::Unauthorized {  })
+        Err(_not_admin) => Err(ContractError::Unauthorized {  })
     }
 }
 
@@ -112,22 +332,93 @@ fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetTokenAmount {addr} => to_binary(&query_token(deps, addr)?),
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Pool {pool_id} => to_binary(&query_pool(deps, pool_id)?),
+        QueryMsg::PoolByLpToken {lp_token} => to_binary(&query_pool_by_lp_token(deps, lp_token)?),
+        QueryMsg::Pools {start_after, limit} => to_binary(&query_pools(deps, start_after, limit)?),
     }
 }
 
 fn query_token(deps: Deps, addr: String) -> StdResult<TokenAmountResponse> {
-    let res = query_balance(deps, addr)?;
+    let config = CONFIG.load(deps.storage)?;
+    let res: BalanceResponse = deps.querier.query_wasm_smart(config.oastro_token, &Cw20QueryMsg::Balance { address: addr })?;
     Ok(TokenAmountResponse { amount: res.balance.u128() })    
 }
 
+fn query_pool(deps: Deps, pool_id: u128) -> StdResult<PoolResponse> {
+    let pool = pools().load(deps.storage, pool_id)?;
+    Ok(PoolResponse { pool_id, pool })
+}
+
+/// Most recently listed pool for an LP token. Only the latest one can still be active.
+fn latest_pool_by_lp_token(deps: Deps, lp_token: &Addr) -> StdResult<Option<(u128, PoolInfo)>> {
+    pools()
+        .idx
+        .lp_token
+        .prefix(lp_token.clone())
+        .range(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+}
+
+fn query_pool_by_lp_token(deps: Deps, lp_token: String) -> StdResult<PoolResponse> {
+    let lp_token_addr = deps.api.addr_validate(&lp_token)?;
+    let (pool_id, pool) = latest_pool_by_lp_token(deps, &lp_token_addr)?
+        .ok_or_else(|| StdError::not_found(format!("pool for {}", lp_token)))?;
+    Ok(PoolResponse { pool_id, pool })
+}
+
+fn query_pools(deps: Deps, start_after: Option<u128>, limit: Option<u32>) -> StdResult<PoolsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let pools = pools()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(pool_id, pool)| PoolResponse { pool_id, pool }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PoolsResponse { pools })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+    use cosmwasm_std::from_binary;
+
+    const ADMIN: &str = "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq";
+    const ASTRO_DENOM: &str = "ibc/EBD5A24C554198EBAF44979C5B4D2C2D312E6EBAB71962C92F735499C7575839";
 
     fn get_instantiate_msg() -> InstantiateMsg{
-        InstantiateMsg { admin: "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq".to_string() }
+        InstantiateMsg {
+            admin: ADMIN.to_string(),
+            astro_token: "astro_token".to_string(),
+            deposit_denom: ASTRO_DENOM.to_string(),
+            astro_staking: "astro_staking".to_string(),
+            xastro_token: "xastro_token".to_string(),
+            octane_booster: "octane_booster".to_string(),
+            octane_staker: "octane_staker".to_string(),
+            reward_pool_code_id: 7,
+            oastro_code_id: 9,
+            marketing: None,
+        }
+    }
+
+    fn add_pool_msg() -> ExecuteMsg {
+        ExecuteMsg::AddPool { lp_token: "lp_token".to_string(), guage: "generator".to_string(), factory: "factory".to_string() }
+    }
+
+    /// instantiates and stands in for the reply that records the oAstro token
+    fn setup(deps: DepsMut) {
+        instantiate(deps, mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+    }
+
+    fn register_token(deps: DepsMut) {
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.oastro_token = Addr::unchecked("oastro_token");
+            Ok(config)
+        }).unwrap();
     }
 
     #[test]
@@ -139,27 +430,69 @@ mod tests {
 
         // we can just call .unwrap() to assert this was a success
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        assert_eq!(1, res.messages.len());
+        assert_eq!(INSTANTIATE_OASTRO_REPLY_ID, res.messages[0].id);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, msg, .. }) => {
+                assert_eq!(9, *code_id);
+                let msg: oastro_token::msg::InstantiateMsg = from_binary(msg).unwrap();
+                assert_eq!(msg.mint.unwrap().minter, mock_env().contract.address.to_string());
+                assert!(msg.initial_balances.is_empty());
+            }
+            msg => panic!("unexpected message {:?}", msg),
+        }
     }
 
     #[test]
     fn stake() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        setup(deps.as_mut());
+        register_token(deps.as_mut());
 
-        let msg = get_instantiate_msg();
-        let info = mock_info("creator", &coins(5, "token"));
-        let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let info = mock_info("anyone", &coins(5, ASTRO_DENOM));
+        let msg = ExecuteMsg::XAstroStake {  };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "astro_staking".to_string(),
+                msg: to_binary(&AstroStakingExecuteMsg::Enter { receiver: Some("octane_booster".to_string()) }).unwrap(),
+                funds: coins(5, ASTRO_DENOM),
+            })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "oastro_token".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: "anyone".to_string(), amount: Uint128::new(5) }).unwrap(),
+                funds: vec![],
+            })
+        );
+
+        // other denoms are not accepted
+        let info = mock_info("anyone", &coins(5, "token"));
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::XAstroStake {  }).unwrap_err();
+        assert!(matches!(err, ContractError::NoDeposit { .. }));
+    }
 
+    #[test]
+    fn add_pool_instantiates_reward_pool() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        setup(deps.as_mut());
 
-        let info = mock_info("anyone", &coins(5, ASTRO_ADDRESS));
-        let msg = ExecuteMsg::XAstroStake {  };
-        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), add_pool_msg()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
 
-       let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTokenAmount { addr: "anyone".to_string() }).unwrap();
-        let value: TokenAmountResponse = from_binary(&res).unwrap();
-        assert_eq!(5, value.amount); 
-        
-        
+        let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_pool_msg()).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(INSTANTIATE_REWARD_POOL_REPLY_ID, res.messages[0].id);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. }) => assert_eq!(7, *code_id),
+            msg => panic!("unexpected message {:?}", msg),
+        }
+
+        // nothing is registered until the reward pool exists
+        assert_eq!(POOL_COUNT.load(&deps.storage).unwrap(), 0);
     }
 
     #[test]
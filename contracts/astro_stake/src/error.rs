@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::ParseReplyError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,11 +7,27 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("Custom Error val: {val:?}")]
-    CustomError { val: String },
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("A pool for LP token {lp_token} already exists")]
+    PoolExists { lp_token: String },
+
+    #[error("Pool {pool_id} is shut down")]
+    PoolShutdown { pool_id: u128 },
+
+    #[error("No {denom} sent")]
+    NoDeposit { denom: String },
+
+    #[error("Token {token} cannot be deposited")]
+    UnsupportedToken { token: String },
+
+    #[error("The staking contract has no xASTRO shares to price {token} against")]
+    NoXAstroShares { token: String },
+
+    #[error("Unknown reply id {id}")]
+    UnknownReply { id: u64 },
 }
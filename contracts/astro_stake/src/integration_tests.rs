@@ -3,6 +3,7 @@ mod tests {
     use crate::helpers::CwTemplateContract;
     use crate::msg::InstantiateMsg;
     use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use cw20::Cw20Coin;
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
@@ -10,14 +11,148 @@ mod tests {
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
+        )
+        .with_reply(crate::contract::reply);
+        Box::new(contract)
+    }
+
+    pub fn contract_reward_pool() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            octane_reward_pool::contract::execute,
+            octane_reward_pool::contract::instantiate,
+            octane_reward_pool::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_oastro() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            oastro_token::contract::execute,
+            oastro_token::contract::instantiate,
+            oastro_token::contract::query,
         );
         Box::new(contract)
     }
 
-    const USER: &str = "USER";
-    const ADMIN: &str = "ADMIN";
+    pub fn contract_booster() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            octane_booster::contract::execute,
+            octane_booster::contract::instantiate,
+            octane_booster::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const USER: &str = "user";
+    const ADMIN: &str = "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq";
     const NATIVE_DENOM: &str = "denom";
 
+    /// Stands in for the Astroport staking contract: takes native or cw20 ASTRO on Enter and
+    /// credits the receiver with xASTRO at the current deposit/shares rate.
+    mod mock_astro_staking {
+        use cosmwasm_std::{
+            from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+            StdResult, Uint128,
+        };
+        use cw20::Cw20ReceiveMsg;
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::{Item, Map};
+        use serde::{Deserialize, Serialize};
+
+        use crate::msg::AstroStakingHookMsg;
+
+        const DENOM: Item<String> = Item::new("denom");
+        const TOTAL_DEPOSIT: Item<Uint128> = Item::new("total_deposit");
+        const TOTAL_SHARES: Item<Uint128> = Item::new("total_shares");
+        const XASTRO: Map<Addr, Uint128> = Map::new("xastro");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        pub struct InstantiateMsg {
+            pub denom: String,
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            Enter { receiver: Option<String> },
+            Receive(Cw20ReceiveMsg),
+            // stands in for the fees that make xASTRO worth more ASTRO over time
+            AddRewards { amount: Uint128 },
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum QueryMsg {
+            TotalShares {},
+            TotalDeposit {},
+            XAstro { address: String },
+        }
+
+        fn enter(deps: DepsMut, receiver: Addr, amount: Uint128) -> StdResult<Response> {
+            let deposit = TOTAL_DEPOSIT.load(deps.storage)?;
+            let shares = TOTAL_SHARES.load(deps.storage)?;
+            let minted = if shares.is_zero() { amount } else { amount.multiply_ratio(shares, deposit) };
+            TOTAL_DEPOSIT.save(deps.storage, &(deposit + amount))?;
+            TOTAL_SHARES.save(deps.storage, &(shares + minted))?;
+            XASTRO.update(deps.storage, receiver, |b| -> StdResult<_> { Ok(b.unwrap_or_default() + minted) })?;
+            Ok(Response::new())
+        }
+
+        fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            match msg {
+                ExecuteMsg::Enter { receiver } => {
+                    let denom = DENOM.load(deps.storage)?;
+                    let amount = info
+                        .funds
+                        .iter()
+                        .find(|c| c.denom == denom)
+                        .map(|c| c.amount)
+                        .ok_or_else(|| StdError::generic_err("no ASTRO sent"))?;
+                    enter(deps, receiver.map(Addr::unchecked).unwrap_or(info.sender), amount)
+                }
+                ExecuteMsg::Receive(wrapper) => {
+                    let AstroStakingHookMsg::Enter { receiver } = from_binary(&wrapper.msg)?;
+                    let receiver = Addr::unchecked(receiver.unwrap_or(wrapper.sender));
+                    enter(deps, receiver, wrapper.amount)
+                }
+                ExecuteMsg::AddRewards { amount } => {
+                    TOTAL_DEPOSIT.update(deps.storage, |d| -> StdResult<_> { Ok(d + amount) })?;
+                    Ok(Response::new())
+                }
+            }
+        }
+
+        fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+            DENOM.save(deps.storage, &msg.denom)?;
+            TOTAL_DEPOSIT.save(deps.storage, &Uint128::zero())?;
+            TOTAL_SHARES.save(deps.storage, &Uint128::zero())?;
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+            match msg {
+                QueryMsg::TotalShares {} => to_binary(&TOTAL_SHARES.load(deps.storage)?),
+                QueryMsg::TotalDeposit {} => to_binary(&TOTAL_DEPOSIT.load(deps.storage)?),
+                QueryMsg::XAstro { address } => to_binary(
+                    &XASTRO.may_load(deps.storage, Addr::unchecked(address))?.unwrap_or_default(),
+                ),
+            }
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
     fn mock_app() -> App {
         AppBuilder::new().build(|router, _, storage| {
             router
@@ -27,18 +162,114 @@ mod tests {
                     &Addr::unchecked(USER),
                     vec![Coin {
                         denom: NATIVE_DENOM.to_string(),
-                        amount: Uint128::new(1),
+                        amount: Uint128::new(1_000),
+                    }],
+                )
+                .unwrap();
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(ADMIN),
+                    vec![Coin {
+                        denom: NATIVE_DENOM.to_string(),
+                        amount: Uint128::new(1_000),
                     }],
                 )
                 .unwrap();
         })
     }
 
-    fn proper_instantiate() -> (App, CwTemplateContract) {
+    fn instantiate_cw20(app: &mut App, cw20_id: u64, symbol: &str, holders: &[&str]) -> Addr {
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(1_000),
+                })
+                .collect(),
+            mint: None,
+            marketing: None,
+        };
+        app.instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], symbol, None)
+            .unwrap()
+    }
+
+    struct Suite {
+        app: App,
+        astro_stake: CwTemplateContract,
+        booster: Addr,
+        astro_staking: Addr,
+        astro: Addr,
+        xastro: Addr,
+        oastro: Addr,
+    }
+
+    fn proper_instantiate() -> (App, CwTemplateContract, Addr) {
+        let suite = setup();
+        (suite.app, suite.astro_stake, suite.booster)
+    }
+
+    fn setup() -> Suite {
+        setup_with_marketing(None)
+    }
+
+    fn setup_with_marketing(marketing: Option<oastro_token::msg::InstantiateMarketingInfo>) -> Suite {
         let mut app = mock_app();
+        let oastro_id = app.store_code(contract_oastro());
         let cw_template_id = app.store_code(contract_template());
+        let reward_pool_id = app.store_code(contract_reward_pool());
+        let booster_id = app.store_code(contract_booster());
+        let cw20_id = app.store_code(contract_cw20());
+        let astro_staking_id = app.store_code(mock_astro_staking::contract());
+
+        let astro_staking = app
+            .instantiate_contract(
+                astro_staking_id,
+                Addr::unchecked(ADMIN),
+                &mock_astro_staking::InstantiateMsg { denom: NATIVE_DENOM.to_string() },
+                &[],
+                "astro_staking",
+                None,
+            )
+            .unwrap();
+
+        let astro = instantiate_cw20(&mut app, cw20_id, "ASTRO", &[USER]);
+        let xastro = instantiate_cw20(&mut app, cw20_id, "XASTRO", &[USER]);
 
-        let msg = InstantiateMsg { admin: "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq".into() };
+        let booster = app
+            .instantiate_contract(
+                booster_id,
+                Addr::unchecked(ADMIN),
+                &octane_booster::msg::InstantiateMsg {
+                    admin: ADMIN.to_string(),
+                    astro_token: astro.to_string(),
+                    xastro_token: xastro.to_string(),
+                    voting_escrow: "voting_escrow".to_string(),
+                    pool_manager: ADMIN.to_string(),
+                },
+                &[],
+                "booster",
+                None,
+            )
+            .unwrap();
+
+        let msg = InstantiateMsg {
+            admin: ADMIN.into(),
+            astro_token: astro.to_string(),
+            deposit_denom: NATIVE_DENOM.to_string(),
+            astro_staking: astro_staking.to_string(),
+            xastro_token: xastro.to_string(),
+            octane_booster: booster.to_string(),
+            octane_staker: "octane_staker".into(),
+            reward_pool_code_id: reward_pool_id,
+            oastro_code_id: oastro_id,
+            marketing,
+        };
         let cw_template_contract_addr = app
             .instantiate_contract(
                 cw_template_id,
@@ -50,22 +281,493 @@ mod tests {
             )
             .unwrap();
 
-        let cw_template_contract = CwTemplateContract(cw_template_contract_addr);
+        // astro_stake registers pools with the booster from now on
+        let msg = octane_booster::msg::ExecuteMsg::SetPoolManager {
+            pool_manager: cw_template_contract_addr.to_string(),
+        };
+        app.execute_contract(Addr::unchecked(ADMIN), booster.clone(), &msg, &[])
+            .unwrap();
 
-        (app, cw_template_contract)
+        let astro_stake = CwTemplateContract(cw_template_contract_addr);
+        let config: crate::state::Config = app
+            .wrap()
+            .query_wasm_smart(astro_stake.addr(), &crate::msg::QueryMsg::Config {})
+            .unwrap();
+
+        Suite { app, astro_stake, booster, astro_staking, astro, xastro, oastro: config.oastro_token }
     }
 
-    mod count {
+    mod stake {
+        use super::*;
+        use crate::msg::{ExecuteMsg, QueryMsg, ReceiveMsg, TokenAmountResponse};
+        use cosmwasm_std::{coins, to_binary};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+        #[test]
+        fn stake_forwards_astro_to_staking_for_the_booster() {
+            let mut suite = setup();
+
+            let msg = suite.astro_stake.call(ExecuteMsg::XAstroStake {}).unwrap();
+            suite.app.execute(Addr::unchecked(USER), msg).unwrap_err();
+
+            let msg = ExecuteMsg::XAstroStake {};
+            suite
+                .app
+                .execute_contract(Addr::unchecked(USER), suite.astro_stake.addr(), &msg, &coins(400, NATIVE_DENOM))
+                .unwrap();
+
+            let res: TokenAmountResponse = suite
+                .app
+                .wrap()
+                .query_wasm_smart(suite.astro_stake.addr(), &QueryMsg::GetTokenAmount { addr: USER.to_string() })
+                .unwrap();
+            assert_eq!(res.amount, 400);
+
+            // nothing is left behind, the staking contract holds the ASTRO and credited the booster
+            let held = suite.app.wrap().query_balance(suite.astro_stake.addr(), NATIVE_DENOM).unwrap();
+            assert!(held.amount.is_zero());
+            let staked = suite.app.wrap().query_balance(&suite.astro_staking, NATIVE_DENOM).unwrap();
+            assert_eq!(staked.amount, Uint128::new(400));
+            let xastro: Uint128 = suite
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &suite.astro_staking,
+                    &mock_astro_staking::QueryMsg::XAstro { address: suite.booster.to_string() },
+                )
+                .unwrap();
+            assert_eq!(xastro, Uint128::new(400));
+        }
+
+        impl Suite {
+            fn send(&mut self, token: &Addr, amount: u128, recipient: Option<&str>) -> Result<(), String> {
+                let msg = Cw20ExecuteMsg::Send {
+                    contract: self.astro_stake.addr().to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&ReceiveMsg::Deposit { recipient: recipient.map(String::from) }).unwrap(),
+                };
+                self.app
+                    .execute_contract(Addr::unchecked(USER), token.clone(), &msg, &[])
+                    .map(|_| ())
+                    .map_err(|err| err.root_cause().to_string())
+            }
+
+            fn oastro(&self, account: &str) -> u128 {
+                let res: TokenAmountResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(self.astro_stake.addr(), &QueryMsg::GetTokenAmount { addr: account.to_string() })
+                    .unwrap();
+                res.amount
+            }
+
+            fn cw20_balance(&self, token: &Addr, account: &Addr) -> Uint128 {
+                let res: BalanceResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: account.to_string() })
+                    .unwrap();
+                res.balance
+            }
+        }
+
+        #[test]
+        fn cw20_astro_is_staked_for_the_booster() {
+            let mut suite = setup();
+            let astro = suite.astro.clone();
+
+            suite.send(&astro, 300, Some("recipient")).unwrap();
+            assert_eq!(suite.oastro("recipient"), 300);
+            assert_eq!(suite.oastro(USER), 0);
+
+            assert_eq!(suite.cw20_balance(&astro, &suite.astro_staking), Uint128::new(300));
+            let xastro: Uint128 = suite
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &suite.astro_staking,
+                    &mock_astro_staking::QueryMsg::XAstro { address: suite.booster.to_string() },
+                )
+                .unwrap();
+            assert_eq!(xastro, Uint128::new(300));
+        }
+
+        #[test]
+        fn cw20_xastro_is_credited_at_the_staking_rate() {
+            let mut suite = setup();
+
+            // 400 shares backed by 600 ASTRO
+            let msg = ExecuteMsg::XAstroStake {};
+            suite
+                .app
+                .execute_contract(Addr::unchecked(USER), suite.astro_stake.addr(), &msg, &coins(400, NATIVE_DENOM))
+                .unwrap();
+            let msg = mock_astro_staking::ExecuteMsg::AddRewards { amount: Uint128::new(200) };
+            suite
+                .app
+                .execute_contract(Addr::unchecked(ADMIN), suite.astro_staking.clone(), &msg, &[])
+                .unwrap();
+
+            let xastro = suite.xastro.clone();
+            suite.send(&xastro, 100, None).unwrap();
+            assert_eq!(suite.oastro(USER), 400 + 150);
+            assert_eq!(suite.cw20_balance(&xastro, &suite.booster), Uint128::new(100));
+        }
+
+        #[test]
+        fn xastro_without_shares_is_rejected() {
+            let mut suite = setup();
+
+            // nothing has been staked yet, so xASTRO has no rate
+            let xastro = suite.xastro.clone();
+            let err = suite.send(&xastro, 100, None).unwrap_err();
+            assert!(err.contains("no xASTRO shares"), "{}", err);
+            assert_eq!(suite.oastro(USER), 0);
+        }
+
+        #[test]
+        fn other_cw20_tokens_are_rejected() {
+            let mut suite = setup();
+            let cw20_id = suite.app.store_code(contract_cw20());
+            let other = instantiate_cw20(&mut suite.app, cw20_id, "OTHER", &[USER]);
+
+            suite.send(&other, 100, None).unwrap_err();
+            assert_eq!(suite.oastro(USER), 0);
+        }
+    }
+
+    mod token {
         use super::*;
         use crate::msg::ExecuteMsg;
+        use cosmwasm_std::coins;
+        use cw20::{AllAccountsResponse, BalanceResponse, Logo, LogoInfo, MarketingInfoResponse, MinterResponse, TokenInfoResponse};
+        use oastro_token::msg::{ExecuteMsg as TokenExecuteMsg, InstantiateMarketingInfo, QueryMsg as TokenQueryMsg};
+
+        fn stake(suite: &mut Suite, amount: u128) {
+            let msg = ExecuteMsg::XAstroStake {};
+            suite
+                .app
+                .execute_contract(Addr::unchecked(USER), suite.astro_stake.addr(), &msg, &coins(amount, NATIVE_DENOM))
+                .unwrap();
+        }
+
+        fn balance(suite: &Suite, address: &str) -> Uint128 {
+            let res: BalanceResponse = suite
+                .app
+                .wrap()
+                .query_wasm_smart(&suite.oastro, &TokenQueryMsg::Balance { address: address.to_string() })
+                .unwrap();
+            res.balance
+        }
+
+        #[test]
+        fn astro_stake_is_the_only_minter() {
+            let mut suite = setup();
+
+            let minter: Option<MinterResponse> = suite
+                .app
+                .wrap()
+                .query_wasm_smart(&suite.oastro, &TokenQueryMsg::Minter {})
+                .unwrap();
+            assert_eq!(minter.unwrap().minter, suite.astro_stake.addr().to_string());
+
+            let msg = TokenExecuteMsg::Mint { recipient: USER.to_string(), amount: Uint128::new(100) };
+            suite.app.execute_contract(Addr::unchecked(USER), suite.oastro.clone(), &msg, &[]).unwrap_err();
+        }
+
+        #[test]
+        fn staking_mints_transferable_oastro() {
+            let mut suite = setup();
+            stake(&mut suite, 500);
+
+            let msg = TokenExecuteMsg::Transfer { recipient: "friend".to_string(), amount: Uint128::new(200) };
+            suite.app.execute_contract(Addr::unchecked(USER), suite.oastro.clone(), &msg, &[]).unwrap();
+
+            assert_eq!(balance(&suite, USER), Uint128::new(300));
+            assert_eq!(balance(&suite, "friend"), Uint128::new(200));
+
+            let info: TokenInfoResponse = suite
+                .app
+                .wrap()
+                .query_wasm_smart(&suite.oastro, &TokenQueryMsg::TokenInfo {})
+                .unwrap();
+            assert_eq!(info.symbol, "oAstro");
+            assert_eq!(info.total_supply, Uint128::new(500));
+
+            let accounts: AllAccountsResponse = suite
+                .app
+                .wrap()
+                .query_wasm_smart(&suite.oastro, &TokenQueryMsg::AllAccounts { start_after: None, limit: None })
+                .unwrap();
+            assert_eq!(accounts.accounts, vec!["friend".to_string(), USER.to_string()]);
+        }
+
+        #[test]
+        fn marketing_info_is_passed_to_the_token() {
+            let suite = setup_with_marketing(Some(InstantiateMarketingInfo {
+                project: Some("octane".to_string()),
+                description: None,
+                marketing: Some("marketing".to_string()),
+                logo: Some(Logo::Url("https://example.com/oastro.png".to_string())),
+            }));
+
+            let info: MarketingInfoResponse = suite
+                .app
+                .wrap()
+                .query_wasm_smart(&suite.oastro, &TokenQueryMsg::MarketingInfo {})
+                .unwrap();
+            assert_eq!(info.project, Some("octane".to_string()));
+            assert_eq!(info.marketing, Some(Addr::unchecked("marketing")));
+            assert_eq!(info.logo, Some(LogoInfo::Url("https://example.com/oastro.png".to_string())));
+        }
+    }
+
+    mod pools {
+        use super::*;
+        use crate::msg::{ExecuteMsg, PoolResponse, PoolsResponse, QueryMsg};
+
+        fn add_pool(app: &mut App, astro_stake: &CwTemplateContract, lp_token: &str) -> Result<(), String> {
+            let msg = ExecuteMsg::AddPool {
+                lp_token: lp_token.to_string(),
+                guage: "generator".to_string(),
+                factory: "factory".to_string(),
+            };
+            app.execute(Addr::unchecked(ADMIN), astro_stake.call(msg).unwrap())
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        }
+
+        fn pools(app: &App, astro_stake: &CwTemplateContract, start_after: Option<u128>, limit: Option<u32>) -> PoolsResponse {
+            app.wrap()
+                .query_wasm_smart(astro_stake.addr(), &QueryMsg::Pools { start_after, limit })
+                .unwrap()
+        }
+
+        #[test]
+        fn add_pool_registers_reward_pool_and_booster_pool() {
+            let (mut app, astro_stake, booster) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            add_pool(&mut app, &astro_stake, "lp_two").unwrap();
+
+            let pools = pools(&app, &astro_stake, None, None).pools;
+            assert_eq!(pools.len(), 2);
+            assert_eq!(pools[1].pool_id, 1);
+            assert_eq!(pools[1].pool.lp_token, Addr::unchecked("lp_two"));
+            assert_eq!(pools[1].pool.guage, Addr::unchecked("generator"));
+            assert_eq!(pools[1].pool.factory, Addr::unchecked("factory"));
+            assert!(pools[1].pool.active);
+            assert_ne!(pools[0].pool.rewards, pools[1].pool.rewards);
+
+            // the new reward pool was initialised for this pool
+            let config: octane_reward_pool::state::Config = app
+                .wrap()
+                .query_wasm_raw(pools[1].pool.rewards.clone(), octane_reward_pool::state::CONFIG.as_slice())
+                .map(|raw| cosmwasm_std::from_slice(&raw.unwrap()).unwrap())
+                .unwrap();
+            assert_eq!(config.lp_token, Addr::unchecked("lp_two"));
+            assert_eq!(config.octane_booster, booster);
+            assert_eq!(config.pool_id, 1);
+
+            // and the booster routes the same pool id to the same reward contract
+            let booster_pool: octane_booster::state::PoolInfo = app
+                .wrap()
+                .query_wasm_smart(&booster, &octane_booster::msg::QueryMsg::Pool { pool_id: 1 })
+                .unwrap();
+            assert_eq!(booster_pool.lp_token, Addr::unchecked("lp_two"));
+            assert_eq!(booster_pool.rewards, pools[1].pool.rewards);
+        }
+
+        #[test]
+        fn add_pool_rejects_duplicates_and_non_admins() {
+            let (mut app, astro_stake, _) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap_err();
+
+            let msg = ExecuteMsg::AddPool {
+                lp_token: "lp_two".to_string(),
+                guage: "generator".to_string(),
+                factory: "factory".to_string(),
+            };
+            app.execute(Addr::unchecked(USER), astro_stake.call(msg).unwrap())
+                .unwrap_err();
+
+            assert_eq!(pools(&app, &astro_stake, None, None).pools.len(), 1);
+        }
+
+        #[test]
+        fn admin_manages_the_reward_schedule_of_new_pools() {
+            let (mut app, astro_stake, _) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            let rewards = pools(&app, &astro_stake, None, None).pools[0].pool.rewards.clone();
+
+            let msg = octane_reward_pool::msg::ExecuteMsg::SetRewardDuration { duration: 86400 };
+            app.execute_contract(Addr::unchecked(USER), rewards.clone(), &msg, &[])
+                .unwrap_err();
+            app.execute_contract(Addr::unchecked(ADMIN), rewards.clone(), &msg, &[])
+                .unwrap();
+
+            let config: octane_reward_pool::state::Config = app
+                .wrap()
+                .query_wasm_raw(rewards, octane_reward_pool::state::CONFIG.as_slice())
+                .map(|raw| cosmwasm_std::from_slice(&raw.unwrap()).unwrap())
+                .unwrap();
+            assert_eq!(config.reward_manager, Addr::unchecked(ADMIN));
+            assert_eq!(config.reward_duration, 86400);
+        }
+
+        #[test]
+        fn admin_manages_reward_tokens_of_new_pools() {
+            use cosmwasm_std::coins;
+            use cw20::Denom;
+            use octane_reward_pool::msg::{ExecuteMsg as RewardPoolExecuteMsg, QueryMsg as RewardPoolQueryMsg, RewardTokensResponse};
+
+            let (mut app, astro_stake, _) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            let rewards = pools(&app, &astro_stake, None, None).pools[0].pool.rewards.clone();
+            let native = Denom::Native(NATIVE_DENOM.to_string());
+
+            let msg = RewardPoolExecuteMsg::AddRewardToken { token: native.clone() };
+            app.execute_contract(Addr::unchecked(USER), rewards.clone(), &msg, &[])
+                .unwrap_err();
+            app.execute_contract(Addr::unchecked(ADMIN), rewards.clone(), &msg, &[])
+                .unwrap();
+
+            // extra reward tokens are funded by the admin directly
+            let msg = RewardPoolExecuteMsg::QueueNewRewards {};
+            app.execute_contract(Addr::unchecked(USER), rewards.clone(), &msg, &coins(100, NATIVE_DENOM))
+                .unwrap_err();
+            app.execute_contract(Addr::unchecked(ADMIN), rewards.clone(), &msg, &coins(100, NATIVE_DENOM))
+                .unwrap();
+
+            let config: crate::state::Config = app.wrap().query_wasm_smart(astro_stake.addr(), &QueryMsg::Config {}).unwrap();
+            let msg = RewardPoolExecuteMsg::RemoveRewardToken { token: Denom::Cw20(config.astro_token) };
+            app.execute_contract(Addr::unchecked(ADMIN), rewards.clone(), &msg, &[])
+                .unwrap_err();
+
+            let res: RewardTokensResponse = app
+                .wrap()
+                .query_wasm_smart(rewards, &RewardPoolQueryMsg::RewardTokens {})
+                .unwrap();
+            assert_eq!(res.tokens.len(), 2);
+            assert!(res.tokens.iter().all(|token| token.active));
+            assert_eq!(res.tokens[1].token, native);
+        }
+
+        #[test]
+        fn pool_can_be_found_by_id_and_lp_token() {
+            let (mut app, astro_stake, _) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            add_pool(&mut app, &astro_stake, "lp_two").unwrap();
+
+            let by_id: PoolResponse = app
+                .wrap()
+                .query_wasm_smart(astro_stake.addr(), &QueryMsg::Pool { pool_id: 1 })
+                .unwrap();
+            let by_lp_token: PoolResponse = app
+                .wrap()
+                .query_wasm_smart(astro_stake.addr(), &QueryMsg::PoolByLpToken { lp_token: "lp_two".to_string() })
+                .unwrap();
+            assert_eq!(by_id, by_lp_token);
+            assert_eq!(by_id.pool_id, 1);
+
+            app.wrap()
+                .query_wasm_smart::<PoolResponse>(astro_stake.addr(), &QueryMsg::Pool { pool_id: 2 })
+                .unwrap_err();
+            app.wrap()
+                .query_wasm_smart::<PoolResponse>(astro_stake.addr(), &QueryMsg::PoolByLpToken { lp_token: "lp_three".to_string() })
+                .unwrap_err();
+        }
 
         #[test]
-        fn count() {
-            let (mut app, cw_template_contract) = proper_instantiate();
+        fn pools_are_paginated_by_id() {
+            let (mut app, astro_stake, _) = proper_instantiate();
 
-            let msg = ExecuteMsg::XAstroStake{};
-            let cosmos_msg = cw_template_contract.call(msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            for lp_token in ["lp_one", "lp_two", "lp_three"] {
+                add_pool(&mut app, &astro_stake, lp_token).unwrap();
+            }
+
+            let page = pools(&app, &astro_stake, None, Some(2)).pools;
+            assert_eq!(page.iter().map(|p| p.pool_id).collect::<Vec<_>>(), vec![0, 1]);
+
+            let page = pools(&app, &astro_stake, Some(1), Some(2)).pools;
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].pool.lp_token, Addr::unchecked("lp_three"));
+
+            assert!(pools(&app, &astro_stake, Some(2), None).pools.is_empty());
+        }
+
+        #[test]
+        fn shutdown_pool_retires_it_everywhere() {
+            let (mut app, astro_stake, booster) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            add_pool(&mut app, &astro_stake, "lp_two").unwrap();
+
+            let msg = ExecuteMsg::ShutdownPool { pool_id: 1 };
+            app.execute(Addr::unchecked(USER), astro_stake.call(msg.clone()).unwrap())
+                .unwrap_err();
+            app.execute(Addr::unchecked(ADMIN), astro_stake.call(msg.clone()).unwrap())
+                .unwrap();
+
+            let pool: PoolResponse = app
+                .wrap()
+                .query_wasm_smart(astro_stake.addr(), &QueryMsg::Pool { pool_id: 1 })
+                .unwrap();
+            assert!(!pool.pool.active);
+            let other: PoolResponse = app
+                .wrap()
+                .query_wasm_smart(astro_stake.addr(), &QueryMsg::Pool { pool_id: 0 })
+                .unwrap();
+            assert!(other.pool.active);
+
+            let booster_pool: octane_booster::state::PoolInfo = app
+                .wrap()
+                .query_wasm_smart(&booster, &octane_booster::msg::QueryMsg::Pool { pool_id: 1 })
+                .unwrap();
+            assert!(booster_pool.shutdown);
+
+            let config: octane_reward_pool::state::Config = app
+                .wrap()
+                .query_wasm_raw(pool.pool.rewards, octane_reward_pool::state::CONFIG.as_slice())
+                .map(|raw| cosmwasm_std::from_slice(&raw.unwrap()).unwrap())
+                .unwrap();
+            assert!(config.shutdown);
+
+            // a pool can only be retired once
+            app.execute(Addr::unchecked(ADMIN), astro_stake.call(msg).unwrap())
+                .unwrap_err();
+        }
+
+        #[test]
+        fn shut_down_lp_token_can_be_relisted() {
+            let (mut app, astro_stake, booster) = proper_instantiate();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            let msg = ExecuteMsg::ShutdownPool { pool_id: 0 };
+            app.execute(Addr::unchecked(ADMIN), astro_stake.call(msg).unwrap())
+                .unwrap();
+
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap();
+            // only one active pool per LP token
+            add_pool(&mut app, &astro_stake, "lp_one").unwrap_err();
+
+            let by_lp_token: PoolResponse = app
+                .wrap()
+                .query_wasm_smart(astro_stake.addr(), &QueryMsg::PoolByLpToken { lp_token: "lp_one".to_string() })
+                .unwrap();
+            assert_eq!(by_lp_token.pool_id, 1);
+            assert!(by_lp_token.pool.active);
+
+            let booster_pool: octane_booster::state::PoolInfo = app
+                .wrap()
+                .query_wasm_smart(&booster, &octane_booster::msg::QueryMsg::Pool { pool_id: 1 })
+                .unwrap();
+            assert_eq!(booster_pool.rewards, by_lp_token.pool.rewards);
         }
     }
 }
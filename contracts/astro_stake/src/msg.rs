@@ -1,16 +1,41 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use cw20::Cw20ReceiveMsg;
+use oastro_token::msg::InstantiateMarketingInfo;
+
+use crate::state::PoolInfo;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: String,
+    pub astro_token: String,
+    pub deposit_denom: String,
+    pub astro_staking: String,
+    pub xastro_token: String,
+    pub octane_booster: String,
+    pub octane_staker: String,
+    pub reward_pool_code_id: u64,
+    pub oastro_code_id: u64,
+    pub marketing: Option<InstantiateMarketingInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     XAstroStake{},
-    AddPool{lp_token: String, guage: String, factory: String}
+    // Receive takes cw20 ASTRO or xASTRO sent with a ReceiveMsg hook
+    Receive(Cw20ReceiveMsg),
+    AddPool{lp_token: String, guage: String, factory: String},
+    // ShutdownPool retires a pool: its LP leaves the gauge and depositors can only withdraw
+    ShutdownPool{pool_id: u128},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    // Deposit mints oAstro to the recipient, or to the sender when none is given
+    Deposit{recipient: Option<String>},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -18,6 +43,11 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     // GetCount returns the current count as a json-encoded number
     GetTokenAmount {addr: String},
+    Config {},
+    Pool {pool_id: u128},
+    // PoolByLpToken returns the latest pool listed for lp_token, a relisted LP replaces its shut down pool
+    PoolByLpToken {lp_token: String},
+    Pools {start_after: Option<u128>, limit: Option<u32>},
 }
 
 // We define a custom struct for each query response
@@ -25,3 +55,35 @@ pub enum QueryMsg {
 pub struct TokenAmountResponse {
     pub amount: u128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub pool_id: u128,
+    pub pool: PoolInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolResponse>,
+}
+
+/// Astroport staking messages, the xASTRO minted on Enter is sent to the receiver
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AstroStakingExecuteMsg {
+    Enter{receiver: Option<String>},
+}
+
+/// Hook understood by the Astroport staking contract when cw20 ASTRO is sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AstroStakingHookMsg {
+    Enter{receiver: Option<String>},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AstroStakingQueryMsg {
+    TotalShares{},
+    TotalDeposit{},
+}
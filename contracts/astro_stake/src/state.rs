@@ -2,7 +2,22 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub astro_token: Addr,
+    // native denom accepted by XAstroStake and forwarded to the Astroport staking contract
+    pub deposit_denom: String,
+    pub astro_staking: Addr,
+    pub xastro_token: Addr,
+    pub octane_booster: Addr,
+    pub octane_staker: Addr,
+    pub reward_pool_code_id: u64,
+    pub oastro_token: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PoolInfo {
@@ -10,13 +25,37 @@ pub struct PoolInfo {
     pub guage: Addr,
     pub rewards: Addr,
     pub factory: Addr,
+    pub active: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Pools{
-    pub items: Vec<PoolInfo>
+pub struct PoolIndexes<'a> {
+    pub lp_token: MultiIndex<'a, Addr, PoolInfo, u128>,
+}
+
+impl<'a> IndexList<PoolInfo> for PoolIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PoolInfo>> + '_> {
+        let v: Vec<&dyn Index<PoolInfo>> = vec![&self.lp_token];
+        Box::new(v.into_iter())
+    }
 }
 
-pub const POOLS_CONTAINER: Item<Pools> = Item::new("pools");
+/// Registry of supported pools keyed by pool_id, looked up by LP token through the index.
+/// An LP token can be listed again once its pool is shut down, so it may map to several pools.
+pub fn pools<'a>() -> IndexedMap<'a, u128, PoolInfo, PoolIndexes<'a>> {
+    let indexes = PoolIndexes {
+        lp_token: MultiIndex::new(|pool| pool.lp_token.clone(), "pools", "pools__lp_token"),
+    };
+    IndexedMap::new("pools", indexes)
+}
+
+pub const POOL_COUNT: Item<u128> = Item::new("pool_count");
+
+/// Pool waiting for its reward contract to be instantiated
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingPool {
+    pub lp_token: Addr,
+    pub guage: Addr,
+    pub factory: Addr,
+}
 
-pub const TMP_POOLS_CONTAINER: Map<Addr, bool> = Map::new("tmp_pools");
\ No newline at end of file
+pub const TMP_POOLS_CONTAINER: Item<PendingPool> = Item::new("tmp_pools");
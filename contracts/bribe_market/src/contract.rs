@@ -0,0 +1,373 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, Uint128, WasmMsg};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::msg::{BribeData, BribesResponse, ExecuteMsg, InstantiateMsg, PendingBribeData, PendingBribesResponse, QueryMsg, ReceiveMsg};
+
+use astro_stake::msg::{PoolResponse, QueryMsg as PoolRegistryQueryMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw_storage_plus::Bound;
+use octane_vote_lock::state::epoch_at;
+use octane_voter::msg::{EpochVotesResponse, LastCastEpochResponse, QueryMsg as VoterQueryMsg, UserVotesResponse};
+use octane_voter::state::pool_weight;
+
+use crate::state::{BRIBE_EPOCHS, BRIBES, Bribe, CLAIMED, CONFIG, Config, DEPOSITS, denom_key};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:bribe_market";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = Config{
+        pool_registry: deps.api.addr_validate(&msg.pool_registry)?,
+        voter: deps.api.addr_validate(&msg.voter)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("pool_registry", msg.pool_registry)
+        .add_attribute("voter", msg.voter))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::DepositBribe { lp_token, epoch } => {
+            let bribes = info.funds.iter().map(|coin| (Denom::Native(coin.denom.clone()), coin.amount.u128())).collect();
+            deposit_bribe(deps, env, info.sender, lp_token, epoch, bribes)
+        },
+        ExecuteMsg::ClaimBribes { epochs, pools } => claim_bribes(deps, info, epochs, pools),
+        ExecuteMsg::RefundBribes { epoch, lp_token } => refund_bribes(deps, info, epoch, lp_token),
+    }
+}
+
+fn receive(deps: DepsMut, env: Env, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match msg {
+        // info.sender is the bribe token that forwarded the hook
+        ReceiveMsg::DepositBribe { lp_token, epoch } => deposit_bribe(deps, env, sender, lp_token, epoch, vec![(Denom::Cw20(info.sender), wrapper.amount.u128())]),
+    }
+}
+
+/// Bribes can be added until the epoch ends. The voter only casts an epoch's votes once
+/// it is over, so nothing is deposited after the outcome is known.
+fn deposit_bribe(deps: DepsMut, env: Env, depositor: Addr, lp_token: String, epoch: u64, bribes: Vec<(Denom, u128)>) -> Result<Response, ContractError>{
+    if epoch < epoch_at(env.block.time.seconds()) {
+        return Err(ContractError::EpochClosed { epoch });
+    }
+    if bribes.is_empty() || bribes.iter().any(|(_, amount)| *amount == 0) {
+        return Err(ContractError::ZeroAmount {  });
+    }
+    let lp_token_addr = query_active_pool(deps.as_ref(), &lp_token)?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "deposit_bribe")
+        .add_attribute("depositor", depositor.clone())
+        .add_attribute("lp_token", lp_token)
+        .add_attribute("epoch", epoch.to_string());
+
+    BRIBE_EPOCHS.save(deps.storage, epoch, &Empty {})?;
+    for (token, amount) in bribes {
+        BRIBES.update(deps.storage, (epoch, lp_token_addr.clone(), denom_key(&token)), |bribe| -> StdResult<_> {
+            let mut bribe = bribe.unwrap_or(Bribe { token: token.clone(), amount: 0 });
+            bribe.amount += amount;
+            Ok(bribe)
+        })?;
+        DEPOSITS.update(deps.storage, ((epoch, lp_token_addr.clone()), depositor.clone(), denom_key(&token)), |deposit| -> StdResult<_> {
+            let mut deposit = deposit.unwrap_or(Bribe { token: token.clone(), amount: 0 });
+            deposit.amount += amount;
+            Ok(deposit)
+        })?;
+        res = res
+            .add_attribute("token", denom_key(&token))
+            .add_attribute("amount", amount.to_string());
+    }
+
+    Ok(res)
+}
+
+fn claim_bribes(deps: DepsMut, info: MessageInfo, mut epochs: Vec<u64>, pools: Option<Vec<String>>) -> Result<Response, ContractError>{
+    let pools = pools
+        .map(|pools| -> StdResult<_> {
+            let mut pools = pools
+                .iter()
+                .map(|pool| deps.api.addr_validate(pool))
+                .collect::<StdResult<Vec<_>>>()?;
+            // a repeated pool would otherwise be paid twice before it is marked as claimed
+            pools.sort();
+            pools.dedup();
+            Ok(pools)
+        })
+        .transpose()?;
+    epochs.sort_unstable();
+    epochs.dedup();
+
+    let mut payouts: Vec<Bribe> = vec![];
+    for epoch in epochs {
+        if !is_finalized(deps.as_ref(), epoch)? {
+            return Err(ContractError::EpochNotFinalized { epoch });
+        }
+        for (lp_token, share) in claimable_bribes(deps.as_ref(), &info.sender, epoch, pools.as_deref())? {
+            CLAIMED.save(deps.storage, ((info.sender.clone(), epoch), lp_token, denom_key(&share.token)), &share.amount)?;
+            match payouts.iter_mut().find(|payout| payout.token == share.token) {
+                Some(payout) => payout.amount += share.amount,
+                None => payouts.push(share),
+            }
+        }
+    }
+    if payouts.is_empty() {
+        return Err(ContractError::NothingToClaim {  });
+    }
+
+    let msgs = payouts
+        .iter()
+        .map(|payout| bribe_transfer_msg(&payout.token, &info.sender, payout.amount))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("method", "claim_bribes")
+        .add_attribute("voter", info.sender))
+}
+
+/// Bribes on a pool that got no votes in a finalized epoch can't be claimed by anyone, so
+/// their depositors take them back. This also covers pools shut down after the deposit.
+fn refund_bribes(deps: DepsMut, info: MessageInfo, epoch: u64, lp_token: String) -> Result<Response, ContractError>{
+    if !is_finalized(deps.as_ref(), epoch)? {
+        return Err(ContractError::EpochNotFinalized { epoch });
+    }
+    let lp_token_addr = deps.api.addr_validate(&lp_token)?;
+    let config = CONFIG.load(deps.storage)?;
+    let epoch_votes: EpochVotesResponse = deps.querier.query_wasm_smart(config.voter, &VoterQueryMsg::EpochVotes { epoch })?;
+    if epoch_votes.votes.iter().any(|vote| vote.pool == lp_token_addr && vote.weight > 0) {
+        return Err(ContractError::PoolHasVotes { lp_token, epoch });
+    }
+
+    let deposit_prefix = ((epoch, lp_token_addr.clone()), info.sender.clone());
+    let deposits = DEPOSITS
+        .prefix(deposit_prefix.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if deposits.is_empty() {
+        return Err(ContractError::NothingToRefund {  });
+    }
+
+    let mut msgs = vec![];
+    for (key, deposit) in deposits {
+        DEPOSITS.remove(deps.storage, (deposit_prefix.0.clone(), deposit_prefix.1.clone(), key.clone()));
+        let bribe_key = (epoch, lp_token_addr.clone(), key);
+        let mut bribe = BRIBES.load(deps.storage, bribe_key.clone())?;
+        bribe.amount -= deposit.amount;
+        if bribe.amount == 0 {
+            BRIBES.remove(deps.storage, bribe_key);
+        } else {
+            BRIBES.save(deps.storage, bribe_key, &bribe)?;
+        }
+        msgs.push(bribe_transfer_msg(&deposit.token, &info.sender, deposit.amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("method", "refund_bribes")
+        .add_attribute("depositor", info.sender)
+        .add_attribute("lp_token", lp_token)
+        .add_attribute("epoch", epoch.to_string()))
+}
+
+/// The voter's unclaimed share of each bribe on the pools it voted for in a finalized epoch,
+/// pro rata to the weight it gave the pool. Rounding dust stays in the contract.
+fn claimable_bribes(deps: Deps, voter: &Addr, epoch: u64, pools: Option<&[Addr]>) -> StdResult<Vec<(Addr, Bribe)>> {
+    let config = CONFIG.load(deps.storage)?;
+    let user_votes: UserVotesResponse = deps.querier.query_wasm_smart(
+        config.voter.clone(),
+        &VoterQueryMsg::UserVotes { addr: voter.to_string(), epoch: Some(epoch) },
+    )?;
+    if user_votes.votes.is_empty() {
+        return Ok(vec![]);
+    }
+    let epoch_votes: EpochVotesResponse = deps.querier.query_wasm_smart(config.voter, &VoterQueryMsg::EpochVotes { epoch })?;
+
+    let mut shares = vec![];
+    for (lp_token, bps) in user_votes.votes {
+        if let Some(pools) = pools {
+            if !pools.contains(&lp_token) {
+                continue;
+            }
+        }
+        let user_weight = pool_weight(user_votes.power, bps);
+        let pool_total = epoch_votes.votes
+            .iter()
+            .find(|vote| vote.pool == lp_token)
+            .map(|vote| vote.weight)
+            .unwrap_or_default();
+        if user_weight == 0 || pool_total == 0 {
+            continue;
+        }
+
+        let bribes = BRIBES
+            .prefix((epoch, lp_token.clone()))
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (key, bribe) in bribes {
+            if CLAIMED.has(deps.storage, ((voter.clone(), epoch), lp_token.clone(), key)) {
+                continue;
+            }
+            let amount = Uint128::new(bribe.amount).multiply_ratio(user_weight, pool_total).u128();
+            if amount > 0 {
+                shares.push((lp_token.clone(), Bribe { token: bribe.token, amount }));
+            }
+        }
+    }
+
+    Ok(shares)
+}
+
+fn bribe_transfer_msg(token: &Denom, recipient: &Addr, amount: u128) -> StdResult<CosmosMsg> {
+    Ok(match token {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount, denom),
+        }.into(),
+        Denom::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) })?,
+            funds: vec![],
+        }.into(),
+    })
+}
+
+fn query_active_pool(deps: Deps, lp_token: &str) -> Result<Addr, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let res: PoolResponse = deps.querier
+        .query_wasm_smart(config.pool_registry, &PoolRegistryQueryMsg::PoolByLpToken { lp_token: lp_token.to_string() })
+        .map_err(|_| ContractError::InvalidPool { lp_token: lp_token.to_string() })?;
+    if !res.pool.active {
+        return Err(ContractError::InvalidPool { lp_token: lp_token.to_string() });
+    }
+    Ok(res.pool.lp_token)
+}
+
+fn is_finalized(deps: Deps, epoch: u64) -> StdResult<bool> {
+    let config = CONFIG.load(deps.storage)?;
+    let res: LastCastEpochResponse = deps.querier.query_wasm_smart(config.voter, &VoterQueryMsg::LastCastEpoch {})?;
+    Ok(matches!(res.epoch, Some(last) if last >= epoch))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::EpochBribes { epoch } => to_binary(&query_epoch_bribes(deps, epoch)?),
+        QueryMsg::PoolBribes { lp_token, epoch } => to_binary(&query_pool_bribes(deps, lp_token, epoch)?),
+        QueryMsg::PendingBribes { addr, start_after, limit } => to_binary(&query_pending_bribes(deps, addr, start_after, limit)?),
+    }
+}
+
+fn query_epoch_bribes(deps: Deps, epoch: u64) -> StdResult<BribesResponse> {
+    let bribes = BRIBES
+        .sub_prefix(epoch)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|((lp_token, _), bribe)| BribeData { lp_token, token: bribe.token, amount: bribe.amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(BribesResponse { epoch, finalized: is_finalized(deps, epoch)?, bribes })
+}
+
+fn query_pool_bribes(deps: Deps, lp_token: String, epoch: u64) -> StdResult<BribesResponse> {
+    let lp_token = deps.api.addr_validate(&lp_token)?;
+    let bribes = BRIBES
+        .prefix((epoch, lp_token.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, bribe)| BribeData { lp_token: lp_token.clone(), token: bribe.token, amount: bribe.amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(BribesResponse { epoch, finalized: is_finalized(deps, epoch)?, bribes })
+}
+
+fn query_pending_bribes(deps: Deps, addr: String, start_after: Option<u64>, limit: Option<u32>) -> StdResult<PendingBribesResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let config = CONFIG.load(deps.storage)?;
+    let last_cast: LastCastEpochResponse = deps.querier.query_wasm_smart(config.voter, &VoterQueryMsg::LastCastEpoch {})?;
+    let last_cast = match last_cast.epoch {
+        Some(epoch) => epoch,
+        None => return Ok(PendingBribesResponse { bribes: vec![], last_epoch: None }),
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let end = Some(Bound::inclusive(last_cast));
+
+    let epochs = BRIBE_EPOCHS
+        .keys(deps.storage, start, end, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut bribes = vec![];
+    for epoch in epochs.iter().copied() {
+        for (lp_token, share) in claimable_bribes(deps, &addr, epoch, None)? {
+            bribes.push(PendingBribeData { epoch, lp_token, token: share.token, amount: share.amount });
+        }
+    }
+
+    Ok(PendingBribesResponse { bribes, last_epoch: epochs.last().copied() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary};
+
+    fn get_instantiate_msg() -> InstantiateMsg{
+        InstantiateMsg { pool_registry: "astro_stake".to_string(), voter: "voter".to_string() }
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&res).unwrap();
+        assert_eq!(config.pool_registry, Addr::unchecked("astro_stake"));
+        assert_eq!(config.voter, Addr::unchecked("voter"));
+    }
+
+    #[test]
+    fn deposit_checks_epoch_and_amount() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        let epoch = epoch_at(mock_env().block.time.seconds());
+
+        let msg = ExecuteMsg::DepositBribe { lp_token: "lp_token".to_string(), epoch: epoch - 1 };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("protocol", &coins(100, "uinj")), msg).unwrap_err();
+        assert!(matches!(err, ContractError::EpochClosed { .. }));
+
+        let msg = ExecuteMsg::DepositBribe { lp_token: "lp_token".to_string(), epoch };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("protocol", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroAmount {}));
+    }
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{lp_token} is not an active pool")]
+    InvalidPool { lp_token: String },
+
+    #[error("Voting for epoch {epoch} has closed")]
+    EpochClosed { epoch: u64 },
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Votes for epoch {epoch} are not finalized yet")]
+    EpochNotFinalized { epoch: u64 },
+
+    #[error("No bribes to claim")]
+    NothingToClaim {},
+
+    #[error("{lp_token} received votes in epoch {epoch}, its bribes go to the voters")]
+    PoolHasVotes { lp_token: String, epoch: u64 },
+
+    #[error("No bribes to refund")]
+    NothingToRefund {},
+}
@@ -0,0 +1,488 @@
+#[cfg(test)]
+mod tests {
+    use crate::helpers::CwTemplateContract;
+    use crate::msg::{BribeData, BribesResponse, ExecuteMsg, InstantiateMsg, PendingBribeData, PendingBribesResponse, QueryMsg, ReceiveMsg};
+    use cosmwasm_std::{coins, to_binary, Addr, Coin, Empty, Uint128};
+    use cw20::{Cw20Coin, Cw20ExecuteMsg, Denom, MinterResponse};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use octane_vote_lock::msg::ReceiveMsg as VoteLockReceiveMsg;
+    use octane_vote_lock::state::{epoch_at, EPOCH_LENGTH};
+    use octane_voter::msg::ExecuteMsg as VoterExecuteMsg;
+
+    pub fn contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_vote_lock() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            octane_vote_lock::contract::execute,
+            octane_vote_lock::contract::instantiate,
+            octane_vote_lock::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_voter() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            octane_voter::contract::execute,
+            octane_voter::contract::instantiate,
+            octane_voter::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const ADMIN: &str = "admin";
+    const PROTOCOL: &str = "protocol";
+    const ALICE: &str = "alice";
+    const BOB: &str = "bob";
+    const CAROL: &str = "carol";
+    const NATIVE_DENOM: &str = "uinj";
+    const LP_A: &str = "lp_a";
+    const LP_B: &str = "lp_b";
+    const LP_RETIRED: &str = "lp_retired";
+
+    /// Stands in for the astro_stake pool registry: answers PoolByLpToken for registered LP tokens
+    mod mock_pool_registry {
+        use astro_stake::msg::{PoolResponse, QueryMsg};
+        use astro_stake::state::PoolInfo;
+        use cosmwasm_std::{to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::Map;
+        use serde::{Deserialize, Serialize};
+
+        const POOLS: Map<Addr, bool> = Map::new("pools");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            AddPool { lp_token: String, active: bool },
+        }
+
+        fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            let ExecuteMsg::AddPool { lp_token, active } = msg;
+            POOLS.save(deps.storage, Addr::unchecked(lp_token), &active)?;
+            Ok(Response::new())
+        }
+
+        fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+            match msg {
+                QueryMsg::PoolByLpToken { lp_token } => {
+                    let lp_token = Addr::unchecked(lp_token);
+                    let active = POOLS.load(deps.storage, lp_token.clone())?;
+                    let pool = PoolInfo {
+                        lp_token,
+                        guage: Addr::unchecked("generator"),
+                        rewards: Addr::unchecked("rewards"),
+                        factory: Addr::unchecked("factory"),
+                        active,
+                    };
+                    to_binary(&PoolResponse { pool_id: 0, pool })
+                }
+                _ => Err(StdError::generic_err("not supported")),
+            }
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    /// Stands in for the Astroport generator controller: accepts the voter's votes
+    mod mock_generator_controller {
+        use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use octane_voter::msg::GeneratorControllerExecuteMsg;
+
+        fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: GeneratorControllerExecuteMsg) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&Empty {})
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    struct Suite {
+        app: App,
+        market: CwTemplateContract,
+        voter: Addr,
+        vote_lock: Addr,
+        lock_token: Addr,
+        token: Addr,
+    }
+
+    fn instantiate_cw20(app: &mut App, symbol: &str, holders: &[&str]) -> Addr {
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(1_000),
+                })
+                .collect(),
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        };
+        app.instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], symbol, None)
+            .unwrap()
+    }
+
+    fn setup() -> Suite {
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(PROTOCOL),
+                    vec![Coin {
+                        denom: NATIVE_DENOM.to_string(),
+                        amount: Uint128::new(1_000),
+                    }],
+                )
+                .unwrap();
+        });
+
+        let token = instantiate_cw20(&mut app, "BRIBE", &[PROTOCOL]);
+        let lock_token = instantiate_cw20(&mut app, "OASTRO", &[ALICE, BOB, CAROL]);
+
+        let registry_id = app.store_code(mock_pool_registry::contract());
+        let registry = app
+            .instantiate_contract(registry_id, Addr::unchecked(ADMIN), &Empty {}, &[], "astro_stake", None)
+            .unwrap();
+        for (lp_token, active) in [(LP_A, true), (LP_B, true), (LP_RETIRED, false)] {
+            let msg = mock_pool_registry::ExecuteMsg::AddPool { lp_token: lp_token.to_string(), active };
+            app.execute_contract(Addr::unchecked(ADMIN), registry.clone(), &msg, &[]).unwrap();
+        }
+
+        let vote_lock_id = app.store_code(contract_vote_lock());
+        let msg = octane_vote_lock::msg::InstantiateMsg { lock_token: lock_token.to_string(), lock_epochs: 4 };
+        let vote_lock = app
+            .instantiate_contract(vote_lock_id, Addr::unchecked(ADMIN), &msg, &[], "vote_lock", None)
+            .unwrap();
+
+        let controller_id = app.store_code(mock_generator_controller::contract());
+        let controller = app
+            .instantiate_contract(controller_id, Addr::unchecked(ADMIN), &Empty {}, &[], "controller", None)
+            .unwrap();
+
+        let voter_id = app.store_code(contract_voter());
+        let msg = octane_voter::msg::InstantiateMsg {
+            admin: ADMIN.to_string(),
+            vote_lock: vote_lock.to_string(),
+            pool_registry: registry.to_string(),
+            generator_controller: controller.to_string(),
+        };
+        let voter = app
+            .instantiate_contract(voter_id, Addr::unchecked(ADMIN), &msg, &[], "voter", None)
+            .unwrap();
+
+        let market_id = app.store_code(contract_template());
+        let msg = InstantiateMsg { pool_registry: registry.to_string(), voter: voter.to_string() };
+        let market = app
+            .instantiate_contract(market_id, Addr::unchecked(ADMIN), &msg, &[], "bribe_market", None)
+            .unwrap();
+
+        Suite { app, market: CwTemplateContract(market), voter, vote_lock, lock_token, token }
+    }
+
+    impl Suite {
+        fn epoch(&self) -> u64 {
+            epoch_at(self.app.block_info().time.seconds())
+        }
+
+        fn advance_epochs(&mut self, epochs: u64) {
+            self.app.update_block(|block| block.time = block.time.plus_seconds(epochs * EPOCH_LENGTH));
+        }
+
+        fn lock(&mut self, account: &str, amount: u128) {
+            let msg = Cw20ExecuteMsg::Send {
+                contract: self.vote_lock.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&VoteLockReceiveMsg::Lock {}).unwrap(),
+            };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.lock_token.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        fn vote(&mut self, account: &str, votes: &[(&str, u16)]) {
+            let votes = votes.iter().map(|(pool, bps)| (pool.to_string(), *bps)).collect();
+            self.app
+                .execute_contract(Addr::unchecked(account), self.voter.clone(), &VoterExecuteMsg::Vote { votes }, &[])
+                .unwrap();
+        }
+
+        /// Moves to the next epoch and casts the votes of the one that ended
+        fn finalize(&mut self) {
+            self.advance_epochs(1);
+            self.app
+                .execute_contract(Addr::unchecked(ADMIN), self.voter.clone(), &VoterExecuteMsg::CastVotes {}, &[])
+                .unwrap();
+        }
+
+        fn claim(&mut self, account: &str, epochs: &[u64], pools: Option<&[&str]>) -> Result<(), String> {
+            let msg = ExecuteMsg::ClaimBribes {
+                epochs: epochs.to_vec(),
+                pools: pools.map(|pools| pools.iter().map(|pool| pool.to_string()).collect()),
+            };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.market.addr(), &msg, &[])
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn pending(&self, account: &str) -> Vec<PendingBribeData> {
+            self.pending_page(account, None, None).bribes
+        }
+
+        fn pending_page(&self, account: &str, start_after: Option<u64>, limit: Option<u32>) -> PendingBribesResponse {
+            let msg = QueryMsg::PendingBribes { addr: account.to_string(), start_after, limit };
+            self.app.wrap().query_wasm_smart(self.market.addr(), &msg).unwrap()
+        }
+
+        fn deposit_native(&mut self, lp_token: &str, epoch: u64, amount: u128) -> Result<(), String> {
+            let msg = ExecuteMsg::DepositBribe { lp_token: lp_token.to_string(), epoch };
+            self.app
+                .execute_contract(Addr::unchecked(PROTOCOL), self.market.addr(), &msg, &coins(amount, NATIVE_DENOM))
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn deposit_cw20(&mut self, lp_token: &str, epoch: u64, amount: u128) -> Result<(), String> {
+            let msg = Cw20ExecuteMsg::Send {
+                contract: self.market.addr().to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&ReceiveMsg::DepositBribe { lp_token: lp_token.to_string(), epoch }).unwrap(),
+            };
+            self.app
+                .execute_contract(Addr::unchecked(PROTOCOL), self.token.clone(), &msg, &[])
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn refund(&mut self, account: &str, epoch: u64, lp_token: &str) -> Result<(), String> {
+            let msg = ExecuteMsg::RefundBribes { epoch, lp_token: lp_token.to_string() };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.market.addr(), &msg, &[])
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn epoch_bribes(&self, epoch: u64) -> BribesResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(self.market.addr(), &QueryMsg::EpochBribes { epoch })
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn bribes_are_tracked_per_epoch_and_pool() {
+        let mut suite = setup();
+        suite.lock(ALICE, 100);
+        suite.advance_epochs(1);
+        let epoch = suite.epoch();
+        let native = Denom::Native(NATIVE_DENOM.to_string());
+        let cw20 = Denom::Cw20(suite.token.clone());
+
+        suite.deposit_native(LP_A, epoch, 100).unwrap();
+        suite.deposit_native(LP_A, epoch, 50).unwrap();
+        suite.deposit_cw20(LP_A, epoch, 200).unwrap();
+        suite.deposit_cw20(LP_B, epoch, 300).unwrap();
+        suite.deposit_native(LP_B, epoch + 1, 400).unwrap();
+
+        let bribes = suite.epoch_bribes(epoch);
+        assert!(!bribes.finalized);
+        assert_eq!(bribes.bribes, vec![
+            BribeData { lp_token: Addr::unchecked(LP_A), token: cw20.clone(), amount: 200 },
+            BribeData { lp_token: Addr::unchecked(LP_A), token: native.clone(), amount: 150 },
+            BribeData { lp_token: Addr::unchecked(LP_B), token: cw20, amount: 300 },
+        ]);
+
+        let pool_bribes = suite.market.pool_bribes::<_, _, Empty>(&suite.app, LP_B, epoch + 1).unwrap();
+        assert_eq!(pool_bribes.bribes, vec![BribeData { lp_token: Addr::unchecked(LP_B), token: native, amount: 400 }]);
+
+        let balance = suite.app.wrap().query_balance(suite.market.addr(), NATIVE_DENOM).unwrap();
+        assert_eq!(balance.amount.u128(), 550);
+
+        // the voter casting the epoch's votes finalizes its bribes
+        suite.vote(ALICE, &[(LP_A, 10_000)]);
+        suite.finalize();
+        assert!(suite.epoch_bribes(epoch).finalized);
+        assert!(!suite.epoch_bribes(epoch + 1).finalized);
+    }
+
+    #[test]
+    fn deposits_are_validated() {
+        let mut suite = setup();
+        let epoch = suite.epoch();
+
+        assert_eq!(suite.deposit_cw20("lp_unknown", epoch, 100).unwrap_err(), "lp_unknown is not an active pool");
+        assert_eq!(suite.deposit_native(LP_RETIRED, epoch, 100).unwrap_err(), "lp_retired is not an active pool");
+
+        suite.deposit_cw20(LP_A, epoch, 100).unwrap();
+        suite.app.update_block(|block| block.time = block.time.plus_seconds(EPOCH_LENGTH));
+        assert_eq!(suite.deposit_cw20(LP_A, epoch, 100).unwrap_err(), format!("Voting for epoch {} has closed", epoch));
+        suite.deposit_cw20(LP_A, epoch + 1, 100).unwrap();
+    }
+
+    #[test]
+    fn bribes_are_shared_pro_rata_to_vote_weight() {
+        let mut suite = setup();
+        suite.lock(ALICE, 300);
+        suite.lock(BOB, 100);
+        suite.advance_epochs(1);
+        let epoch = suite.epoch();
+        let native = Denom::Native(NATIVE_DENOM.to_string());
+        let cw20 = Denom::Cw20(suite.token.clone());
+
+        suite.deposit_native(LP_A, epoch, 1_000).unwrap();
+        suite.deposit_cw20(LP_B, epoch, 400).unwrap();
+        suite.vote(ALICE, &[(LP_A, 10_000)]);
+        suite.vote(BOB, &[(LP_A, 5_000), (LP_B, 5_000)]);
+
+        assert_eq!(suite.claim(BOB, &[epoch], Some(&[LP_A])).unwrap_err(), format!("Votes for epoch {} are not finalized yet", epoch));
+        assert!(suite.pending(BOB).is_empty());
+        suite.finalize();
+
+        // LP_A got 300 weight from alice and 50 from bob, LP_B only bob's 50
+        assert_eq!(suite.pending(ALICE), vec![
+            PendingBribeData { epoch, lp_token: Addr::unchecked(LP_A), token: native.clone(), amount: 857 },
+        ]);
+        assert_eq!(suite.pending(BOB), vec![
+            PendingBribeData { epoch, lp_token: Addr::unchecked(LP_A), token: native.clone(), amount: 142 },
+            PendingBribeData { epoch, lp_token: Addr::unchecked(LP_B), token: cw20.clone(), amount: 400 },
+        ]);
+
+        // a repeated pool is only paid once
+        suite.claim(BOB, &[epoch], Some(&[LP_A, LP_A])).unwrap();
+        let bank = |app: &App, account: &str| app.wrap().query_balance(account, NATIVE_DENOM).unwrap().amount.u128();
+        assert_eq!(bank(&suite.app, BOB), 142);
+        assert_eq!(suite.pending(BOB), vec![
+            PendingBribeData { epoch, lp_token: Addr::unchecked(LP_B), token: cw20, amount: 400 },
+        ]);
+        assert_eq!(suite.claim(BOB, &[epoch], Some(&[LP_A])).unwrap_err(), "No bribes to claim");
+
+        suite.claim(BOB, &[epoch], Some(&[LP_B])).unwrap();
+        let res: cw20::BalanceResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(&suite.token, &cw20::Cw20QueryMsg::Balance { address: BOB.to_string() })
+            .unwrap();
+        assert_eq!(res.balance.u128(), 400);
+
+        // without a pool list every voted pool is claimed
+        suite.claim(ALICE, &[epoch], None).unwrap();
+        assert_eq!(bank(&suite.app, ALICE), 857);
+        assert!(suite.pending(ALICE).is_empty());
+        assert_eq!(suite.claim(CAROL, &[epoch], Some(&[LP_A])).unwrap_err(), "No bribes to claim");
+    }
+
+    #[test]
+    fn bribes_on_pools_without_votes_are_refunded() {
+        let mut suite = setup();
+        suite.lock(ALICE, 100);
+        suite.advance_epochs(1);
+        let epoch = suite.epoch();
+
+        suite.deposit_native(LP_A, epoch, 100).unwrap();
+        suite.deposit_native(LP_B, epoch, 300).unwrap();
+        suite.deposit_cw20(LP_B, epoch, 200).unwrap();
+        suite.vote(ALICE, &[(LP_A, 10_000)]);
+
+        assert_eq!(suite.refund(PROTOCOL, epoch, LP_B).unwrap_err(), format!("Votes for epoch {} are not finalized yet", epoch));
+        suite.finalize();
+
+        assert_eq!(suite.refund(PROTOCOL, epoch, LP_A).unwrap_err(), format!("lp_a received votes in epoch {}, its bribes go to the voters", epoch));
+        assert_eq!(suite.refund(ALICE, epoch, LP_B).unwrap_err(), "No bribes to refund");
+
+        suite.refund(PROTOCOL, epoch, LP_B).unwrap();
+        let native = suite.app.wrap().query_balance(PROTOCOL, NATIVE_DENOM).unwrap();
+        assert_eq!(native.amount.u128(), 1_000 - 100);
+        let res: cw20::BalanceResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(&suite.token, &cw20::Cw20QueryMsg::Balance { address: PROTOCOL.to_string() })
+            .unwrap();
+        assert_eq!(res.balance.u128(), 1_000);
+
+        assert_eq!(suite.epoch_bribes(epoch).bribes, vec![
+            BribeData { lp_token: Addr::unchecked(LP_A), token: Denom::Native(NATIVE_DENOM.to_string()), amount: 100 },
+        ]);
+        assert_eq!(suite.refund(PROTOCOL, epoch, LP_B).unwrap_err(), "No bribes to refund");
+    }
+
+    #[test]
+    fn pending_bribes_are_paged_by_epoch() {
+        let mut suite = setup();
+        suite.lock(ALICE, 100);
+        suite.advance_epochs(1);
+
+        let mut epochs = vec![];
+        for _ in 0..3 {
+            let epoch = suite.epoch();
+            suite.deposit_native(LP_A, epoch, 100).unwrap();
+            suite.vote(ALICE, &[(LP_A, 10_000)]);
+            suite.finalize();
+            epochs.push(epoch);
+        }
+        // bribes on the epoch still being voted on are not listed
+        suite.deposit_native(LP_A, suite.epoch(), 100).unwrap();
+
+        let bribe = |epoch| PendingBribeData {
+            epoch,
+            lp_token: Addr::unchecked(LP_A),
+            token: Denom::Native(NATIVE_DENOM.to_string()),
+            amount: 100,
+        };
+        let page = suite.pending_page(ALICE, None, Some(2));
+        assert_eq!(page.bribes, vec![bribe(epochs[0]), bribe(epochs[1])]);
+        assert_eq!(page.last_epoch, Some(epochs[1]));
+
+        let page = suite.pending_page(ALICE, page.last_epoch, Some(2));
+        assert_eq!(page.bribes, vec![bribe(epochs[2])]);
+        assert_eq!(page.last_epoch, Some(epochs[2]));
+
+        let page = suite.pending_page(ALICE, page.last_epoch, Some(2));
+        assert!(page.bribes.is_empty());
+        assert_eq!(page.last_epoch, None);
+
+        // epochs without anything to claim still move the page along
+        let page = suite.pending_page(BOB, None, Some(2));
+        assert!(page.bribes.is_empty());
+        assert_eq!(page.last_epoch, Some(epochs[1]));
+    }
+}
@@ -0,0 +1,72 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw20::{Cw20ReceiveMsg, Denom};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub pool_registry: String,
+    pub voter: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Receive takes cw20 bribes sent with a ReceiveMsg hook
+    Receive(Cw20ReceiveMsg),
+    // DepositBribe locks the attached native tokens as bribes for votes on lp_token's pool in epoch
+    DepositBribe{lp_token: String, epoch: u64},
+    // ClaimBribes pays the sender's share of the bribes on pools it voted for in finalized epochs,
+    // limited to the given pools or from every pool it voted for when none are given
+    ClaimBribes{epochs: Vec<u64>, pools: Option<Vec<String>>},
+    // RefundBribes returns the sender's deposits on a pool that got no votes in a finalized epoch
+    RefundBribes{epoch: u64, lp_token: String},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    DepositBribe{lp_token: String, epoch: u64},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    // EpochBribes lists the bribes on every pool for epoch
+    EpochBribes {epoch: u64},
+    PoolBribes {lp_token: String, epoch: u64},
+    // PendingBribes lists what addr can claim, a page of up to limit finalized epochs after start_after
+    PendingBribes {addr: String, start_after: Option<u64>, limit: Option<u32>},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BribeData {
+    pub lp_token: Addr,
+    pub token: Denom,
+    pub amount: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BribesResponse {
+    pub epoch: u64,
+    // bribes stay locked until the voter has cast the epoch's votes
+    pub finalized: bool,
+    pub bribes: Vec<BribeData>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBribeData {
+    pub epoch: u64,
+    pub lp_token: Addr,
+    pub token: Denom,
+    pub amount: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBribesResponse {
+    pub bribes: Vec<PendingBribeData>,
+    // last epoch read for this page, pass it as start_after for the next one
+    pub last_epoch: Option<u64>,
+}
@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty};
+use cw20::Denom;
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config{
+    /// astro_stake contract whose pools can be bribed
+    pub pool_registry: Addr,
+    /// octane_voter contract that finalizes each epoch's votes
+    pub voter: Addr
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bribe{
+    pub token: Denom,
+    pub amount: u128
+}
+
+/// Bribes deposited for votes on a pool, keyed by (epoch, lp_token, denom_key)
+pub const BRIBES: Map<(u64, Addr, String), Bribe> = Map::new("bribes");
+
+/// Epochs that have had a bribe deposited, so pending bribes can be paged by epoch
+pub const BRIBE_EPOCHS: Map<u64, Empty> = Map::new("bribe_epochs");
+
+/// What each depositor put into a bribe, keyed by ((epoch, lp_token), depositor, denom_key)
+pub const DEPOSITS: Map<((u64, Addr), Addr, String), Bribe> = Map::new("deposits");
+
+/// Amount each voter claimed from a bribe, keyed by ((voter, epoch), lp_token, denom_key)
+pub const CLAIMED: Map<((Addr, u64), Addr, String), u128> = Map::new("claimed");
+
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(token) => format!("cw20:{}", token),
+    }
+}
@@ -0,0 +1,329 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, WasmMsg};
+use cw2::set_contract_version;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, IsClaimedResponse, QueryMsg, ReceiveMsg};
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw_controllers::Admin;
+
+use crate::state::{BITMAP_WORD_BITS, CLAIMED_BITMAP, CONFIG, Config, MERKLE_ROOTS, MerkleRoot, denom_key};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:merkle_distributor";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const SUPER_ADMIN: &Admin = &Admin::new("SUPER_ADMIN");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
+    SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
+
+    let config = Config{
+        keeper: msg.keeper.map(|keeper| deps.api.addr_validate(&keeper)).transpose()?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("owner", msg.admin))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive(deps, info, msg),
+        ExecuteMsg::RegisterMerkleRoot { epoch, merkle_root } => {
+            if info.funds.len() != 1 {
+                return Err(ContractError::InvalidFunds {  });
+            }
+            let coin = &info.funds[0];
+            register_merkle_root(deps, &info.sender, epoch, Denom::Native(coin.denom.clone()), merkle_root, coin.amount.u128())
+        },
+        ExecuteMsg::Claim { epoch, token, index, amount, proof } => claim(deps, info, epoch, token, index, amount, proof),
+        ExecuteMsg::SetKeeper { keeper } => set_keeper(deps, info, keeper),
+        ExecuteMsg::ReclaimUnclaimed { epoch, token } => reclaim_unclaimed(deps, info, epoch, token),
+    }
+}
+
+fn receive(deps: DepsMut, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match msg {
+        // info.sender is the distributed token that forwarded the hook
+        ReceiveMsg::RegisterMerkleRoot { epoch, merkle_root } => register_merkle_root(deps, &sender, epoch, Denom::Cw20(info.sender), merkle_root, wrapper.amount.u128()),
+    }
+}
+
+/// Roots can't be replaced once registered, a correction is posted as a new distribution
+/// under another epoch so that bitmap indices of the old one keep their meaning.
+/// total_amount is what was sent along with the root, each distribution only pays out of its own escrow.
+fn register_merkle_root(deps: DepsMut, sender: &Addr, epoch: u64, token: Denom, merkle_root: String, total_amount: u128) -> Result<Response, ContractError>{
+    check_keeper(deps.as_ref(), sender)?;
+    if total_amount == 0 {
+        return Err(ContractError::ZeroAmount {  });
+    }
+    decode_hash(&merkle_root, "merkle_root")?;
+
+    let key = (epoch, denom_key(&token));
+    if MERKLE_ROOTS.has(deps.storage, key.clone()) {
+        return Err(ContractError::RootExists { epoch, token: key.1 });
+    }
+    MERKLE_ROOTS.save(deps.storage, key.clone(), &MerkleRoot{
+        token,
+        merkle_root: merkle_root.clone(),
+        total_amount,
+        claimed_amount: 0,
+        reclaimed_amount: 0,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "register_merkle_root")
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("token", key.1)
+        .add_attribute("merkle_root", merkle_root)
+        .add_attribute("total_amount", total_amount.to_string()))
+}
+
+fn claim(deps: DepsMut, info: MessageInfo, epoch: u64, token: Denom, index: u64, amount: u128, proof: Vec<String>) -> Result<Response, ContractError>{
+    if amount == 0 {
+        return Err(ContractError::ZeroAmount {  });
+    }
+    let key = (epoch, denom_key(&token));
+    let mut root = MERKLE_ROOTS.load(deps.storage, key.clone())?;
+    if root.reclaimed_amount > 0 {
+        return Err(ContractError::DistributionClosed { epoch, token: key.1 });
+    }
+
+    let word_key = (epoch, key.1.clone(), index / BITMAP_WORD_BITS);
+    let word = CLAIMED_BITMAP.may_load(deps.storage, word_key.clone())?.unwrap_or_default();
+    let bit = 1u128 << (index % BITMAP_WORD_BITS);
+    if word & bit != 0 {
+        return Err(ContractError::AlreadyClaimed { index });
+    }
+
+    let expected = decode_hash(&root.merkle_root, "merkle_root")?;
+    let mut hash = leaf_hash(index, &info.sender, amount);
+    for sibling in proof.iter() {
+        hash = hash_pair(&hash, &decode_hash(sibling, "proof")?);
+    }
+    if hash != expected {
+        return Err(ContractError::VerificationFailed {  });
+    }
+
+    root.claimed_amount += amount;
+    if root.claimed_amount > root.total_amount {
+        return Err(ContractError::ExceedsTotal {  });
+    }
+    MERKLE_ROOTS.save(deps.storage, key.clone(), &root)?;
+    CLAIMED_BITMAP.save(deps.storage, word_key, &(word | bit))?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&root.token, &info.sender, amount)?)
+        .add_attribute("method", "claim")
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("token", key.1)
+        .add_attribute("index", index.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn set_keeper(mut deps: DepsMut, info: MessageInfo, keeper: Option<String>) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let keeper_addr = keeper.as_ref().map(|keeper| deps.api.addr_validate(keeper)).transpose()?;
+    CONFIG.save(deps.storage, &Config{ keeper: keeper_addr })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_keeper")
+        .add_attribute("keeper", keeper.unwrap_or_default()))
+}
+
+fn reclaim_unclaimed(mut deps: DepsMut, info: MessageInfo, epoch: u64, token: Denom) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info.clone())?;
+
+    let key = (epoch, denom_key(&token));
+    let mut root = MERKLE_ROOTS.load(deps.storage, key.clone())?;
+    if root.reclaimed_amount > 0 {
+        return Err(ContractError::DistributionClosed { epoch, token: key.1 });
+    }
+    let unclaimed = root.total_amount - root.claimed_amount;
+    if unclaimed == 0 {
+        return Err(ContractError::NothingToReclaim {  });
+    }
+    root.reclaimed_amount = unclaimed;
+    MERKLE_ROOTS.save(deps.storage, key.clone(), &root)?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&root.token, &info.sender, unclaimed)?)
+        .add_attribute("method", "reclaim_unclaimed")
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("token", key.1)
+        .add_attribute("amount", unclaimed.to_string()))
+}
+
+/// Leaves are sha256("{index}:{address}:{amount}"), with amount in base units.
+pub fn leaf_hash(index: u64, address: &Addr, amount: u128) -> [u8; 32] {
+    Sha256::digest(format!("{}:{}:{}", index, address, amount).as_bytes()).into()
+}
+
+/// Pairs are hashed in sorted order, so proofs don't need to say which side a sibling is on.
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    if a <= b {
+        hasher.update(a);
+        hasher.update(b);
+    } else {
+        hasher.update(b);
+        hasher.update(a);
+    }
+    hasher.finalize().into()
+}
+
+fn decode_hash(hash: &str, what: &str) -> Result<[u8; 32], ContractError>{
+    let mut buf = [0u8; 32];
+    hex::decode_to_slice(hash, &mut buf).map_err(|_| ContractError::InvalidHex { what: what.to_string() })?;
+    Ok(buf)
+}
+
+fn transfer_msg(token: &Denom, recipient: &Addr, amount: u128) -> StdResult<CosmosMsg> {
+    Ok(match token {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount, denom),
+        }.into(),
+        Denom::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) })?,
+            funds: vec![],
+        }.into(),
+    })
+}
+
+fn check_keeper(deps: Deps, sender: &Addr) -> Result<(), ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if config.keeper.as_ref() == Some(sender) || SUPER_ADMIN.is_admin(deps, sender)? {
+        return Ok(());
+    }
+    Err(ContractError::Unauthorized {  })
+}
+
+fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
+    let super_admin = SUPER_ADMIN.assert_admin(deps.as_ref(), &info.sender);
+
+    match super_admin {
+        Ok(()) => Ok(()),
+        Err(_not_admin) => Err(ContractError::Unauthorized {  })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::MerkleRoot { epoch, token } => to_binary(&MERKLE_ROOTS.load(deps.storage, (epoch, denom_key(&token)))?),
+        QueryMsg::IsClaimed { epoch, token, index } => to_binary(&query_is_claimed(deps, epoch, token, index)?),
+    }
+}
+
+fn query_is_claimed(deps: Deps, epoch: u64, token: Denom, index: u64) -> StdResult<IsClaimedResponse> {
+    let word = CLAIMED_BITMAP.may_load(deps.storage, (epoch, denom_key(&token), index / BITMAP_WORD_BITS))?.unwrap_or_default();
+    Ok(IsClaimedResponse { is_claimed: word & (1u128 << (index % BITMAP_WORD_BITS)) != 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::coin;
+
+    const ADMIN: &str = "admin";
+
+    fn get_instantiate_msg() -> InstantiateMsg{
+        InstantiateMsg { admin: ADMIN.to_string(), keeper: Some("keeper".to_string()) }
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&res).unwrap();
+        assert_eq!(config.keeper, Some(Addr::unchecked("keeper")));
+    }
+
+    #[test]
+    fn register_merkle_root_checks() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        let root = hex::encode(leaf_hash(0, &Addr::unchecked("alice"), 100));
+        let token = Denom::Native("uinj".to_string());
+
+        let msg = ExecuteMsg::RegisterMerkleRoot { epoch: 1, merkle_root: root.clone() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &coins(100, "uinj")), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidFunds {}));
+        let funds = vec![coin(100, "uinj"), coin(100, "uatom")];
+        let err = execute(deps.as_mut(), mock_env(), mock_info("keeper", &funds), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidFunds {}));
+
+        let bad = ExecuteMsg::RegisterMerkleRoot { epoch: 1, merkle_root: "abcd".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("keeper", &coins(100, "uinj")), bad).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidHex { .. }));
+
+        execute(deps.as_mut(), mock_env(), mock_info("keeper", &coins(100, "uinj")), msg.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &coins(100, "uinj")), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RootExists { epoch: 1, .. }));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MerkleRoot { epoch: 1, token }).unwrap();
+        let root: MerkleRoot = from_binary(&res).unwrap();
+        assert_eq!(root.total_amount, 100);
+    }
+
+    #[test]
+    fn zero_amount_leaves_are_rejected() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        let root = hex::encode(leaf_hash(0, &Addr::unchecked("alice"), 0));
+        let msg = ExecuteMsg::RegisterMerkleRoot { epoch: 1, merkle_root: root };
+        execute(deps.as_mut(), mock_env(), mock_info("keeper", &coins(100, "uinj")), msg).unwrap();
+
+        let msg = ExecuteMsg::Claim { epoch: 1, token: Denom::Native("uinj".to_string()), index: 0, amount: 0, proof: vec![] };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroAmount {}));
+    }
+
+    #[test]
+    fn set_keeper() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        let msg = ExecuteMsg::SetKeeper { keeper: None };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&res).unwrap();
+        assert_eq!(config.keeper, None);
+    }
+}
@@ -0,0 +1,38 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Exactly one coin must be sent to fund the distribution")]
+    InvalidFunds {},
+
+    #[error("Invalid hex in {what}")]
+    InvalidHex { what: String },
+
+    #[error("A merkle root for {token} is already registered in epoch {epoch}")]
+    RootExists { epoch: u64, token: String },
+
+    #[error("Index {index} was already claimed")]
+    AlreadyClaimed { index: u64 },
+
+    #[error("Merkle proof verification failed")]
+    VerificationFailed {},
+
+    #[error("Claim exceeds the unclaimed total of the root")]
+    ExceedsTotal {},
+
+    #[error("The distribution of {token} in epoch {epoch} was closed")]
+    DistributionClosed { epoch: u64, token: String },
+
+    #[error("Nothing left to reclaim")]
+    NothingToReclaim {},
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use crate::contract::{hash_pair, leaf_hash};
+    use crate::helpers::CwTemplateContract;
+    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg};
+    use crate::state::MerkleRoot;
+    use cosmwasm_std::{coins, to_binary, Addr, Coin, Empty, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Denom};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    pub fn contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const ADMIN: &str = "admin";
+    const KEEPER: &str = "keeper";
+    const ALICE: &str = "alice";
+    const BOB: &str = "bob";
+    const CAROL: &str = "carol";
+    const NATIVE_DENOM: &str = "uinj";
+
+    /// Leaves of the test distribution. Carol's index lands in the second bitmap word.
+    const LEAVES: [(u64, &str, u128); 3] = [(0, ALICE, 100), (1, BOB, 200), (130, CAROL, 300)];
+    const TOTAL: u128 = 600;
+
+    /// Tree of LEAVES: root = H(H(alice, bob), carol). Returns the root and each leaf's proof.
+    fn build_tree() -> (String, Vec<Vec<String>>) {
+        let leaves: Vec<[u8; 32]> = LEAVES.iter().map(|(index, addr, amount)| leaf_hash(*index, &Addr::unchecked(*addr), *amount)).collect();
+        let node = hash_pair(&leaves[0], &leaves[1]);
+        let root = hash_pair(&node, &leaves[2]);
+        let proofs = vec![
+            vec![hex::encode(leaves[1]), hex::encode(leaves[2])],
+            vec![hex::encode(leaves[0]), hex::encode(leaves[2])],
+            vec![hex::encode(node)],
+        ];
+        (hex::encode(root), proofs)
+    }
+
+    struct Suite {
+        app: App,
+        distributor: CwTemplateContract,
+        token: Addr,
+    }
+
+    fn setup() -> Suite {
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(KEEPER),
+                    vec![Coin {
+                        denom: NATIVE_DENOM.to_string(),
+                        amount: Uint128::new(10_000),
+                    }],
+                )
+                .unwrap();
+        });
+
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: "OASTRO".to_string(),
+            symbol: "OASTRO".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: KEEPER.to_string(),
+                amount: Uint128::new(10_000),
+            }],
+            mint: None,
+            marketing: None,
+        };
+        let token = app
+            .instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], "OASTRO", None)
+            .unwrap();
+
+        let distributor_id = app.store_code(contract_template());
+        let msg = InstantiateMsg { admin: ADMIN.to_string(), keeper: Some(KEEPER.to_string()) };
+        let distributor = app
+            .instantiate_contract(distributor_id, Addr::unchecked(ADMIN), &msg, &[], "merkle_distributor", None)
+            .unwrap();
+
+        Suite { app, distributor: CwTemplateContract(distributor), token }
+    }
+
+    impl Suite {
+        /// Registers the test root for token, funded with TOTAL of it
+        fn register(&mut self, epoch: u64, token: Denom) {
+            let (merkle_root, _) = build_tree();
+            match token {
+                Denom::Native(denom) => {
+                    let msg = ExecuteMsg::RegisterMerkleRoot { epoch, merkle_root };
+                    self.app
+                        .execute_contract(Addr::unchecked(KEEPER), self.distributor.addr(), &msg, &coins(TOTAL, denom))
+                        .unwrap();
+                },
+                Denom::Cw20(token) => {
+                    let msg = Cw20ExecuteMsg::Send {
+                        contract: self.distributor.addr().to_string(),
+                        amount: Uint128::new(TOTAL),
+                        msg: to_binary(&ReceiveMsg::RegisterMerkleRoot { epoch, merkle_root }).unwrap(),
+                    };
+                    self.app
+                        .execute_contract(Addr::unchecked(KEEPER), token, &msg, &[])
+                        .unwrap();
+                },
+            }
+        }
+
+        fn reclaim(&mut self, account: &str, epoch: u64, token: Denom) -> Result<(), String> {
+            let msg = ExecuteMsg::ReclaimUnclaimed { epoch, token };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.distributor.addr(), &msg, &[])
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn claim(&mut self, account: &str, epoch: u64, token: Denom, leaf: usize, amount: u128) -> Result<(), String> {
+            let (_, proofs) = build_tree();
+            let msg = ExecuteMsg::Claim { epoch, token, index: LEAVES[leaf].0, amount, proof: proofs[leaf].clone() };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.distributor.addr(), &msg, &[])
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn is_claimed(&self, epoch: u64, token: Denom, index: u64) -> bool {
+            self.distributor
+                .is_claimed::<_, Empty>(&self.app, epoch, token, index)
+                .unwrap()
+                .is_claimed
+        }
+
+        fn cw20_balance(&self, account: &str) -> u128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(&self.token, &Cw20QueryMsg::Balance { address: account.to_string() })
+                .unwrap();
+            res.balance.u128()
+        }
+    }
+
+    #[test]
+    fn leaves_claim_once_with_a_valid_proof() {
+        let mut suite = setup();
+        let native = Denom::Native(NATIVE_DENOM.to_string());
+        suite.register(1, native.clone());
+
+        for (leaf, (index, account, amount)) in LEAVES.iter().enumerate() {
+            assert!(!suite.is_claimed(1, native.clone(), *index));
+            suite.claim(account, 1, native.clone(), leaf, *amount).unwrap();
+            assert!(suite.is_claimed(1, native.clone(), *index));
+            let balance = suite.app.wrap().query_balance(*account, NATIVE_DENOM).unwrap();
+            assert_eq!(balance.amount.u128(), *amount);
+        }
+
+        let err = suite.claim(ALICE, 1, native.clone(), 0, 100).unwrap_err();
+        assert_eq!(err, "Index 0 was already claimed");
+
+        let root: MerkleRoot = suite
+            .app
+            .wrap()
+            .query_wasm_smart(suite.distributor.addr(), &QueryMsg::MerkleRoot { epoch: 1, token: native })
+            .unwrap();
+        assert_eq!(root.claimed_amount, TOTAL);
+    }
+
+    #[test]
+    fn invalid_claims_are_rejected() {
+        let mut suite = setup();
+        let cw20 = Denom::Cw20(suite.token.clone());
+        suite.register(1, cw20.clone());
+
+        // wrong amount, wrong claimant and a distribution that was never registered
+        let err = suite.claim(ALICE, 1, cw20.clone(), 0, 150).unwrap_err();
+        assert_eq!(err, "Merkle proof verification failed");
+        let err = suite.claim(BOB, 1, cw20.clone(), 0, 100).unwrap_err();
+        assert_eq!(err, "Merkle proof verification failed");
+        suite.claim(ALICE, 2, cw20.clone(), 0, 100).unwrap_err();
+        suite.claim(ALICE, 1, Denom::Native(NATIVE_DENOM.to_string()), 0, 100).unwrap_err();
+
+        suite.claim(CAROL, 1, cw20.clone(), 2, 300).unwrap();
+        assert_eq!(suite.cw20_balance(CAROL), 300);
+        assert!(suite.is_claimed(1, cw20.clone(), 130));
+        assert!(!suite.is_claimed(1, cw20, 2));
+    }
+
+    #[test]
+    fn unclaimed_remainder_is_reclaimed_by_admin() {
+        let mut suite = setup();
+        let cw20 = Denom::Cw20(suite.token.clone());
+        let native = Denom::Native(NATIVE_DENOM.to_string());
+        suite.register(1, cw20.clone());
+        suite.register(2, cw20.clone());
+        suite.register(1, native.clone());
+        suite.claim(ALICE, 1, cw20.clone(), 0, 100).unwrap();
+
+        assert_eq!(suite.reclaim(KEEPER, 1, cw20.clone()).unwrap_err(), "Unauthorized");
+        suite.reclaim(ADMIN, 1, cw20.clone()).unwrap();
+        assert_eq!(suite.cw20_balance(ADMIN), TOTAL - 100);
+
+        // the closed distribution pays nothing more, the other epoch keeps its own escrow
+        let err = suite.claim(BOB, 1, cw20.clone(), 1, 200).unwrap_err();
+        assert_eq!(err, format!("The distribution of cw20:{} in epoch 1 was closed", suite.token));
+        assert_eq!(suite.reclaim(ADMIN, 1, cw20.clone()).unwrap_err(), format!("The distribution of cw20:{} in epoch 1 was closed", suite.token));
+        suite.claim(BOB, 2, cw20.clone(), 1, 200).unwrap();
+        assert_eq!(suite.cw20_balance(BOB), 200);
+        assert_eq!(suite.cw20_balance(suite.distributor.addr().as_str()), TOTAL - 200);
+
+        for (leaf, (_, account, amount)) in LEAVES.iter().enumerate() {
+            suite.claim(account, 1, native.clone(), leaf, *amount).unwrap();
+        }
+        assert_eq!(suite.reclaim(ADMIN, 1, native).unwrap_err(), "Nothing left to reclaim");
+    }
+}
@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw20::{Cw20ReceiveMsg, Denom};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub keeper: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Receive takes the cw20 funding of a distribution sent with a ReceiveMsg hook
+    Receive(Cw20ReceiveMsg),
+    // RegisterMerkleRoot posts the root of an epoch's distribution, funded by the single native coin attached
+    RegisterMerkleRoot{epoch: u64, merkle_root: String},
+    // Claim pays the sender the amount of its leaf, proof is the list of hex encoded sibling hashes
+    Claim{epoch: u64, token: Denom, index: u64, amount: u128, proof: Vec<String>},
+    SetKeeper{keeper: Option<String>},
+    // ReclaimUnclaimed sends what is left of a distribution to the admin and closes it to further claims
+    ReclaimUnclaimed{epoch: u64, token: Denom},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    RegisterMerkleRoot{epoch: u64, merkle_root: String},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    MerkleRoot {epoch: u64, token: Denom},
+    IsClaimed {epoch: u64, token: Denom, index: u64},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw20::Denom;
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config{
+    /// Keeper allowed to register roots besides the admin
+    pub keeper: Option<Addr>
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRoot{
+    pub token: Denom,
+    /// hex encoded sha256 root
+    pub merkle_root: String,
+    /// Amount escrowed when the root was registered, claims never pay out more than this
+    pub total_amount: u128,
+    pub claimed_amount: u128,
+    /// Unclaimed remainder taken back by the admin, the distribution is closed once it is set
+    pub reclaimed_amount: u128
+}
+
+/// Roots registered for a distribution, keyed by (epoch, denom_key)
+pub const MERKLE_ROOTS: Map<(u64, String), MerkleRoot> = Map::new("merkle_roots");
+
+/// Number of leaf indices tracked by each word of the claimed bitmap
+pub const BITMAP_WORD_BITS: u64 = 128;
+
+/// Claimed bitmap of a distribution, keyed by (epoch, denom_key, word index)
+pub const CLAIMED_BITMAP: Map<(u64, String, u64), u128> = Map::new("claimed_bitmap");
+
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(token) => format!("cw20:{}", token),
+    }
+}
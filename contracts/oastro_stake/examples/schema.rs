@@ -3,7 +3,7 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use oastro_stake::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use oastro_stake::msg::{ExecuteMsg, InstantiateMsg, PendingRewardsResponse, QueryMsg, RewardDenomsResponse};
 // use oastro_stake::state::Pools;
 
 fn main() {
@@ -15,6 +15,8 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(PendingRewardsResponse), &out_dir);
+    export_schema(&schema_for!(RewardDenomsResponse), &out_dir);
     // export_schema(&schema_for!(Pools), &out_dir);
     // export_schema(&schema_for!(TokenAmountResponse), &out_dir);
 }
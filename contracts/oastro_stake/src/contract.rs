@@ -1,25 +1,27 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128, WasmMsg};
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, PendingRewardsResponse, QueryMsg, ReceiveMsg, RewardAmount, RewardDenomsResponse};
+use crate::state::{denom_key, RewardIndex, MEMBER_REWARD_PER_WEIGHT, PENDING_REWARDS, REWARD_INDEXES};
 
 use cw_controllers::Admin;
 
-use cw4_stake::state::{CONFIG, Config};
-use cw4_stake::msg::StakedResponse;
-use cw4_stake::contract::{execute_bond, execute_unbond, query_staked};
+use cw4_stake::state::{CLAIMS, CONFIG, Config, HOOKS, MEMBERS, STAKE, TOTAL};
+use cw4_stake::contract::{execute_bond, execute_claim, execute_receive, execute_unbond, query_staked};
+use cw4_stake::msg::QueryMsg as Cw4QueryMsg;
 use cw4_stake::ContractError as CW4_ContractError;
 
-use cw20::{Denom, Balance};
+use cw20::{Balance, Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw_utils::Duration;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:astro_stake";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const SUPER_ADMIN: &Admin = &Admin::new("SUPER_ADMIN");
-const ASTRO_ADDRESS: &str  = "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq"; //"ibc/EBD5A24C554198EBAF44979C5B4D2C2D312E6EBAB71962C92F735499C7575839";
+const REWARD_PRECISION: u128 = 100_000_000_000_000_000_000;
 
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -29,19 +31,30 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    // let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
-    // SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
+    let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
+    SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
     
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     
+    let denom = match msg.denom {
+        Denom::Native(denom) => Denom::Native(denom),
+        Denom::Cw20(token) => Denom::Cw20(deps.api.addr_validate(token.as_str())?),
+    };
+
+    if msg.tokens_per_weight.is_zero() {
+        return Err(ContractError::ZeroTokensPerWeight {});
+    }
+
     let config = Config{
-        denom: Denom::Native(ASTRO_ADDRESS.into()),
-        tokens_per_weight: 1_000_000u128.into(),
-        min_bond: 1u128.into(),
-        unbonding_period: cw_utils::Duration::Time(86400)
+        denom,
+        tokens_per_weight: msg.tokens_per_weight,
+        // min_bond is at least 1, so 0 stake -> non-membership
+        min_bond: std::cmp::max(msg.min_bond, Uint128::new(1)),
+        unbonding_period: msg.unbonding_period
     };
 
     CONFIG.save(deps.storage, &config)?;
+    TOTAL.save(deps.storage, &0)?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
@@ -55,11 +68,236 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, CW4_ContractError> {
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::OAstroStake {} => execute_bond(deps, env,Balance::from(info.funds), info.sender),
-        ExecuteMsg::OAstroUnstake { amount } => execute_unbond(deps, env, info, amount.into())
+        ExecuteMsg::OAstroStake {} => bond(deps, env, info),
+        ExecuteMsg::OAstroUnstake { amount } => unbond(deps, env, info, amount),
+        ExecuteMsg::Claim {} => claim(deps, env, info),
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { tokens_per_weight, min_bond, unbonding_period } => update_config(deps, info, tokens_per_weight, min_bond, unbonding_period),
+        ExecuteMsg::AddHook { addr } => add_hook(deps, info, addr),
+        ExecuteMsg::RemoveHook { addr } => remove_hook(deps, info, addr),
+        ExecuteMsg::AddRewardDenom { denom } => add_reward_denom(deps, info, denom),
+        ExecuteMsg::DistributeRewards {} => {
+            let rewards = info.funds.into_iter().map(|coin| (Denom::Native(coin.denom), coin.amount)).collect();
+            distribute_rewards(deps, rewards)
+        },
+        ExecuteMsg::ClaimRewards {} => claim_rewards(deps, info),
+    }
+}
+
+fn add_hook(mut deps: DepsMut, info: MessageInfo, addr: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.add_hook(deps.storage, hook)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_hook")
+        .add_attribute("hook", addr))
+}
+
+fn remove_hook(mut deps: DepsMut, info: MessageInfo, addr: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.remove_hook(deps.storage, hook)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_hook")
+        .add_attribute("hook", addr))
+}
+
+fn bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError>{
+    let balance = Balance::from(info.funds);
+    check_bond(deps.as_ref(), &balance, &info.sender)?;
+    // settle rewards at the old weight before cw4_stake changes it
+    checkpoint(deps.storage, &info.sender)?;
+
+    Ok(execute_bond(deps, env, balance, info.sender)?)
+}
+
+fn unbond(deps: DepsMut, env: Env, info: MessageInfo, amount: u128) -> Result<Response, ContractError>{
+    checkpoint(deps.storage, &info.sender)?;
+
+    Ok(execute_unbond(deps, env, info, amount.into())?)
+}
+
+fn receive(deps: DepsMut, env: Env, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    match from_binary(&wrapper.msg)? {
+        ReceiveMsg::Bond {} => {
+            // info.sender is the cw20 contract that forwarded the hook
+            let balance = Balance::Cw20(Cw20CoinVerified { address: info.sender.clone(), amount: wrapper.amount });
+            let sender = deps.api.addr_validate(&wrapper.sender)?;
+            check_bond(deps.as_ref(), &balance, &sender)?;
+            checkpoint(deps.storage, &sender)?;
+
+            Ok(execute_receive(deps, env, info, wrapper)?)
+        },
+        ReceiveMsg::DistributeRewards {} => distribute_rewards(deps, vec![(Denom::Cw20(info.sender), wrapper.amount)]),
+    }
+}
+
+/// cw4_stake accepts any stake below min_bond without weight and reports denom
+/// mismatches with its own errors, so bonds are checked here first
+fn check_bond(deps: Deps, balance: &Balance, sender: &Addr) -> Result<(), ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+
+    let amount = match (&config.denom, balance) {
+        (Denom::Native(denom), Balance::Native(funds)) if funds.0.len() == 1 && &funds.0[0].denom == denom => funds.0[0].amount,
+        (Denom::Cw20(token), Balance::Cw20(coin)) if coin.address == *token => coin.amount,
+        (Denom::Native(denom), _) => return Err(ContractError::WrongDenom { expected: denom.clone() }),
+        (Denom::Cw20(token), _) => return Err(ContractError::WrongDenom { expected: token.to_string() }),
+    };
+
+    let stake = STAKE.may_load(deps.storage, sender)?.unwrap_or_default();
+    if stake + amount < config.min_bond {
+        return Err(ContractError::BelowMinBond { min_bond: config.min_bond });
+    }
+
+    Ok(())
+}
+
+fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError>{
+    match execute_claim(deps, env, info) {
+        Err(CW4_ContractError::NothingToClaim {}) => Err(ContractError::NothingToClaim {}),
+        res => Ok(res?),
+    }
+}
+
+fn add_reward_denom(mut deps: DepsMut, info: MessageInfo, denom: Denom) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let denom = match denom {
+        Denom::Native(denom) => Denom::Native(denom),
+        Denom::Cw20(token) => Denom::Cw20(deps.api.addr_validate(token.as_str())?),
+    };
+    let key = denom_key(&denom);
+    if REWARD_INDEXES.has(deps.storage, key.clone()) {
+        return Err(ContractError::RewardDenomExists { denom: key });
+    }
+    REWARD_INDEXES.save(deps.storage, key.clone(), &RewardIndex { denom, reward_per_weight: 0, undistributed: 0 })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_reward_denom")
+        .add_attribute("denom", key))
+}
+
+fn distribute_rewards(deps: DepsMut, rewards: Vec<(Denom, Uint128)>) -> Result<Response, ContractError>{
+    if rewards.iter().all(|(_, amount)| amount.is_zero()) {
+        return Err(ContractError::NoRewards {});
+    }
+
+    let total_weight = TOTAL.load(deps.storage)? as u128;
+    let mut res = Response::new().add_attribute("method", "distribute_rewards");
+    for (denom, amount) in rewards {
+        let key = denom_key(&denom);
+        let mut index = REWARD_INDEXES.may_load(deps.storage, key.clone())?
+            .ok_or(ContractError::UnsupportedRewardDenom { denom: key.clone() })?;
+
+        let amount = index.undistributed + amount.u128();
+        if total_weight == 0 {
+            index.undistributed = amount;
+        } else {
+            index.reward_per_weight += Uint128::new(amount).multiply_ratio(REWARD_PRECISION, total_weight).u128();
+            index.undistributed = 0;
+        }
+        REWARD_INDEXES.save(deps.storage, key.clone(), &index)?;
+
+        res = res.add_attribute(key, amount.to_string());
+    }
+
+    Ok(res)
+}
+
+fn claim_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError>{
+    checkpoint(deps.storage, &info.sender)?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    for index in reward_indexes(deps.storage)? {
+        let key = (denom_key(&index.denom), info.sender.clone());
+        let amount = PENDING_REWARDS.may_load(deps.storage, key.clone())?.unwrap_or_default();
+        if amount == 0 {
+            continue;
+        }
+        PENDING_REWARDS.remove(deps.storage, key);
+
+        msgs.push(match index.denom {
+            Denom::Native(denom) => BankMsg::Send { to_address: info.sender.to_string(), amount: coins(amount, denom) }.into(),
+            Denom::Cw20(token) => WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: info.sender.to_string(), amount: Uint128::new(amount) })?,
+                funds: vec![],
+            }.into(),
+        });
+    }
+    if msgs.is_empty() {
+        return Err(ContractError::NoRewards {});
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("method", "claim_rewards")
+        .add_attribute("member", info.sender))
+}
+
+/// Moves everything a member earned at their current weight into PENDING_REWARDS.
+/// Must run before any change to the member's weight.
+fn checkpoint(storage: &mut dyn Storage, member: &Addr) -> StdResult<()> {
+    let weight = MEMBERS.may_load(storage, member)?.unwrap_or_default() as u128;
+    for index in reward_indexes(storage)? {
+        let key = (denom_key(&index.denom), member.clone());
+        let paid = MEMBER_REWARD_PER_WEIGHT.may_load(storage, key.clone())?.unwrap_or_default();
+        if paid == index.reward_per_weight {
+            continue;
+        }
+
+        let earned = earned_since(weight, paid, index.reward_per_weight);
+        if earned > 0 {
+            PENDING_REWARDS.update(storage, key.clone(), |pending| -> StdResult<_> { Ok(pending.unwrap_or_default() + earned) })?;
+        }
+        MEMBER_REWARD_PER_WEIGHT.save(storage, key, &index.reward_per_weight)?;
     }
+    Ok(())
+}
+
+fn reward_indexes(storage: &dyn Storage) -> StdResult<Vec<RewardIndex>> {
+    REWARD_INDEXES
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, index)| index))
+        .collect()
+}
+
+fn earned_since(weight: u128, from: u128, to: u128) -> u128 {
+    Uint128::new(weight).multiply_ratio(to - from, REWARD_PRECISION).u128()
+}
+
+/// Pending claims keep the release time computed when they were unbonded,
+/// so a new unbonding period only applies to later unbonds.
+fn update_config(mut deps: DepsMut, info: MessageInfo, tokens_per_weight: Option<Uint128>, min_bond: Option<Uint128>, unbonding_period: Option<Duration>) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if let Some(tokens_per_weight) = tokens_per_weight {
+        if tokens_per_weight.is_zero() {
+            return Err(ContractError::ZeroTokensPerWeight {});
+        }
+        config.tokens_per_weight = tokens_per_weight;
+    }
+    if let Some(min_bond) = min_bond {
+        config.min_bond = std::cmp::max(min_bond, Uint128::new(1));
+    }
+    if let Some(unbonding_period) = unbonding_period {
+        config.unbonding_period = unbonding_period;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_config")
+        .add_attribute("tokens_per_weight", config.tokens_per_weight)
+        .add_attribute("min_bond", config.min_bond))
 }
 
 fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
@@ -67,57 +305,309 @@ fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
     
     match super_admin {
         Ok(()) => Ok(()),
-        Err(_not_admin) => return Err(ContractError::Unauthorized {  })
+        Err(_not_admin) => Err(ContractError::Unauthorized {  })
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Staked {addr} => to_binary(&query_staked(deps, addr)?),
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Claims {addr} => to_binary(&CLAIMS.query_claims(deps, &deps.api.addr_validate(&addr)?)?),
+        QueryMsg::Member {addr, at_height} => cw4_stake::contract::query(deps, env, Cw4QueryMsg::Member { addr, at_height }),
+        QueryMsg::ListMembers {start_after, limit} => cw4_stake::contract::query(deps, env, Cw4QueryMsg::ListMembers { start_after, limit }),
+        QueryMsg::TotalWeight {} => cw4_stake::contract::query(deps, env, Cw4QueryMsg::TotalWeight {}),
+        QueryMsg::Hooks {} => to_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::PendingRewards {addr} => to_binary(&query_pending_rewards(deps, addr)?),
+        QueryMsg::RewardDenoms {} => to_binary(&query_reward_denoms(deps)?),
     }
 }
 
+fn query_pending_rewards(deps: Deps, addr: String) -> StdResult<PendingRewardsResponse> {
+    let member = deps.api.addr_validate(&addr)?;
+    let weight = MEMBERS.may_load(deps.storage, &member)?.unwrap_or_default() as u128;
+
+    let rewards = reward_indexes(deps.storage)?
+        .into_iter()
+        .map(|index| {
+            let key = (denom_key(&index.denom), member.clone());
+            let paid = MEMBER_REWARD_PER_WEIGHT.may_load(deps.storage, key.clone())?.unwrap_or_default();
+            let pending = PENDING_REWARDS.may_load(deps.storage, key)?.unwrap_or_default();
+            let amount = pending + earned_since(weight, paid, index.reward_per_weight);
+            Ok(RewardAmount { denom: index.denom, amount: Uint128::new(amount) })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(PendingRewardsResponse { rewards })
+}
+
+fn query_reward_denoms(deps: Deps) -> StdResult<RewardDenomsResponse> {
+    let denoms = reward_indexes(deps.storage)?.into_iter().map(|index| index.denom).collect();
+    Ok(RewardDenomsResponse { denoms })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+    use cosmwasm_std::{coins, from_binary, Addr, Uint128};
+    use cw20::Cw20ReceiveMsg;
+    use cw4_stake::msg::StakedResponse;
+    use cw4_stake::state::CLAIMS;
+    use cw_controllers::HooksResponse;
+    use cw_utils::Expiration;
+
+    const BOND_DENOM: &str = "uoastro";
+    const OASTRO_TOKEN: &str = "oastro";
+    const STAKER: &str = "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcp";
 
-    fn get_instantiate_msg() -> InstantiateMsg{
-        InstantiateMsg { admin: "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq".to_string() }
+    const ADMIN: &str = "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq";
+    const UNBONDING_PERIOD: u64 = 86400;
+
+    fn get_instantiate_msg(denom: Denom) -> InstantiateMsg{
+        InstantiateMsg {
+            admin: ADMIN.to_string(),
+            denom,
+            tokens_per_weight: Uint128::new(1_000_000),
+            min_bond: Uint128::new(1),
+            unbonding_period: Duration::Time(UNBONDING_PERIOD),
+        }
+    }
+
+    fn query_stake(deps: Deps, addr: &str) -> u128 {
+        let res = query(deps, mock_env(), QueryMsg::Staked { addr: addr.to_string() }).unwrap();
+        let value: StakedResponse = from_binary(&res).unwrap();
+        value.stake.u128()
     }
 
     #[test]
     fn proper_initialization() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = get_instantiate_msg();
+        let msg = get_instantiate_msg(Denom::Cw20(Addr::unchecked(OASTRO_TOKEN)));
         let info = mock_info("creator", &coins(1000, "earth"));
 
         // we can just call .unwrap() to assert this was a success
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.denom, Denom::Cw20(Addr::unchecked(OASTRO_TOKEN)));
     }
 
     #[test]
     fn bond() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = get_instantiate_msg();
-        let info = mock_info("creator", &coins(5, ASTRO_ADDRESS));
+        let msg = get_instantiate_msg(Denom::Native(BOND_DENOM.to_string()));
+        let info = mock_info("creator", &[]);
         let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-
-        let info = mock_info("inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcp", &coins(5u128, ASTRO_ADDRESS));
+        let info = mock_info(STAKER, &coins(5u128, BOND_DENOM));
         let msg = ExecuteMsg::OAstroStake {  };
-        let _res = execute(deps.as_mut(), mock_env(), info, msg);
-        // println!("{:#?}", _res);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-       let res = query(deps.as_ref(), mock_env(), QueryMsg::Staked { addr: "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcp".to_string() }).unwrap();
-        let value: StakedResponse = from_binary(&res).unwrap();
-        assert_eq!(5u128, value.stake.u128()); 
-        
-        
+        assert_eq!(5u128, query_stake(deps.as_ref(), STAKER));
+    }
+
+    #[test]
+    fn bond_cw20() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = get_instantiate_msg(Denom::Cw20(Addr::unchecked(OASTRO_TOKEN)));
+        let info = mock_info("creator", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: STAKER.to_string(),
+            amount: Uint128::new(7),
+            msg: to_binary(&ReceiveMsg::Bond {}).unwrap(),
+        });
+
+        // only the configured token can bond through the hook
+        let info = mock_info("othertoken", &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::WrongDenom { .. }));
+
+        let info = mock_info(OASTRO_TOKEN, &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(7u128, query_stake(deps.as_ref(), STAKER));
+
+        // native funds are rejected when bonding a cw20
+        let info = mock_info(STAKER, &coins(5u128, BOND_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap_err();
+        assert_eq!(err.to_string(), format!("Must send {} to stake", OASTRO_TOKEN));
+    }
+
+    #[test]
+    fn update_config() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = get_instantiate_msg(Denom::Native(BOND_DENOM.to_string()));
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let info = mock_info(STAKER, &coins(10u128, BOND_DENOM));
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap();
+        let info = mock_info(STAKER, &[]);
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroUnstake { amount: 4 }).unwrap();
+
+        let msg = ExecuteMsg::UpdateConfig {
+            tokens_per_weight: None,
+            min_bond: Some(Uint128::new(5)),
+            unbonding_period: Some(Duration::Time(2 * UNBONDING_PERIOD)),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(STAKER, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let zero_weight = ExecuteMsg::UpdateConfig { tokens_per_weight: Some(Uint128::zero()), min_bond: None, unbonding_period: None };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), zero_weight).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroTokensPerWeight {}));
+
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&res).unwrap();
+        assert_eq!(config.min_bond, Uint128::new(5));
+        assert_eq!(config.tokens_per_weight, Uint128::new(1_000_000));
+        assert_eq!(config.unbonding_period, Duration::Time(2 * UNBONDING_PERIOD));
+
+        // the pending claim keeps the old unbonding period, new unbonds use the new one
+        let info = mock_info(STAKER, &[]);
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroUnstake { amount: 1 }).unwrap();
+
+        let claims = CLAIMS.query_claims(deps.as_ref(), &Addr::unchecked(STAKER)).unwrap().claims;
+        let now = mock_env().block.time;
+        assert_eq!(claims.len(), 2);
+        assert_eq!(claims[0].release_at, Expiration::AtTime(now.plus_seconds(UNBONDING_PERIOD)));
+        assert_eq!(claims[1].release_at, Expiration::AtTime(now.plus_seconds(2 * UNBONDING_PERIOD)));
+    }
+
+    #[test]
+    fn hooks() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = get_instantiate_msg(Denom::Native(BOND_DENOM.to_string()));
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let add_hook = ExecuteMsg::AddHook { addr: "distributor".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(STAKER, &[]), add_hook.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_hook).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap();
+        let hooks: HooksResponse = from_binary(&res).unwrap();
+        assert_eq!(hooks.hooks, vec!["distributor".to_string()]);
+
+        // bonding notifies the hook of the new member
+        let info = mock_info(STAKER, &coins(1_000_000u128, BOND_DENOM));
+        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let remove_hook = ExecuteMsg::RemoveHook { addr: "distributor".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), remove_hook.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), remove_hook).unwrap_err();
+        assert!(matches!(err, ContractError::Hook(_)));
+
+        let info = mock_info(STAKER, &coins(1_000_000u128, BOND_DENOM));
+        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap();
+        assert_eq!(res.messages.len(), 0);
+    }
+
+    #[test]
+    fn bond_and_claim_errors() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = InstantiateMsg { min_bond: Uint128::new(10), ..get_instantiate_msg(Denom::Native(BOND_DENOM.to_string())) };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let info = mock_info(STAKER, &coins(10u128, "uatom"));
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap_err();
+        assert!(matches!(err, ContractError::WrongDenom { .. }));
+
+        let info = mock_info(STAKER, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap_err();
+        assert!(matches!(err, ContractError::WrongDenom { .. }));
+
+        let info = mock_info(STAKER, &coins(9u128, BOND_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap_err();
+        assert!(matches!(err, ContractError::BelowMinBond { min_bond } if min_bond == Uint128::new(10)));
+
+        // topping up an existing stake only needs to reach min_bond in total
+        let info = mock_info(STAKER, &coins(10u128, BOND_DENOM));
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap();
+        let info = mock_info(STAKER, &coins(1u128, BOND_DENOM));
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::OAstroStake {  }).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(STAKER, &[]), ExecuteMsg::Claim {}).unwrap_err();
+        assert!(matches!(err, ContractError::NothingToClaim {}));
+    }
+
+    fn pending_rewards(deps: Deps, addr: &str) -> Vec<u128> {
+        let res = query(deps, mock_env(), QueryMsg::PendingRewards { addr: addr.to_string() }).unwrap();
+        let value: PendingRewardsResponse = from_binary(&res).unwrap();
+        value.rewards.iter().map(|reward| reward.amount.u128()).collect()
+    }
+
+    #[test]
+    fn rewards_are_shared_by_weight() {
+        const OTHER_STAKER: &str = "inj1other";
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = get_instantiate_msg(Denom::Native(BOND_DENOM.to_string()));
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let add_denom = ExecuteMsg::AddRewardDenom { denom: Denom::Cw20(Addr::unchecked("astro")) };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(STAKER, &[]), add_denom.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_denom.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_denom).unwrap_err();
+        assert!(matches!(err, ContractError::RewardDenomExists { .. }));
+        let add_denom = ExecuteMsg::AddRewardDenom { denom: Denom::Native("uinj".to_string()) };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_denom).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RewardDenoms {}).unwrap();
+        let denoms: RewardDenomsResponse = from_binary(&res).unwrap();
+        assert_eq!(denoms.denoms.len(), 2);
+        assert!(denoms.denoms.contains(&Denom::Cw20(Addr::unchecked("astro"))));
+
+        // rewards sent before anyone bonded wait for the first bonder
+        execute(deps.as_mut(), mock_env(), mock_info("booster", &coins(100, "uinj")), ExecuteMsg::DistributeRewards {}).unwrap();
+
+        execute(deps.as_mut(), mock_env(), mock_info(STAKER, &coins(1_000_000, BOND_DENOM)), ExecuteMsg::OAstroStake {  }).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info(OTHER_STAKER, &coins(3_000_000, BOND_DENOM)), ExecuteMsg::OAstroStake {  }).unwrap();
+
+        execute(deps.as_mut(), mock_env(), mock_info("booster", &coins(300, "uinj")), ExecuteMsg::DistributeRewards {}).unwrap();
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "booster".to_string(),
+            amount: Uint128::new(800),
+            msg: to_binary(&ReceiveMsg::DistributeRewards {}).unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info("astro", &[]), msg).unwrap();
+
+        // reward denoms are listed by key, cw20:astro before native:uinj
+        assert_eq!(pending_rewards(deps.as_ref(), STAKER), vec![200, 100]);
+        assert_eq!(pending_rewards(deps.as_ref(), OTHER_STAKER), vec![600, 300]);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("booster", &coins(100, "uatom")), ExecuteMsg::DistributeRewards {}).unwrap_err();
+        assert!(matches!(err, ContractError::UnsupportedRewardDenom { .. }));
+
+        // bonding more checkpoints the old weight first
+        execute(deps.as_mut(), mock_env(), mock_info(STAKER, &coins(2_000_000, BOND_DENOM)), ExecuteMsg::OAstroStake {  }).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("booster", &coins(600, "uinj")), ExecuteMsg::DistributeRewards {}).unwrap();
+        assert_eq!(pending_rewards(deps.as_ref(), STAKER), vec![200, 400]);
+        assert_eq!(pending_rewards(deps.as_ref(), OTHER_STAKER), vec![600, 600]);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(STAKER, &[]), ExecuteMsg::ClaimRewards {}).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(pending_rewards(deps.as_ref(), STAKER), vec![0, 0]);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(STAKER, &[]), ExecuteMsg::ClaimRewards {}).unwrap_err();
+        assert!(matches!(err, ContractError::NoRewards {}));
+
+        // unbonded weight stops earning
+        execute(deps.as_mut(), mock_env(), mock_info(OTHER_STAKER, &[]), ExecuteMsg::OAstroUnstake { amount: 3_000_000 }).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("booster", &coins(300, "uinj")), ExecuteMsg::DistributeRewards {}).unwrap();
+        assert_eq!(pending_rewards(deps.as_ref(), STAKER), vec![0, 300]);
+        assert_eq!(pending_rewards(deps.as_ref(), OTHER_STAKER), vec![600, 600]);
     }
 }
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_controllers::HookError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,11 +7,33 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("{0}")]
+    Cw4Stake(#[from] cw4_stake::ContractError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("Custom Error val: {val:?}")]
-    CustomError { val: String },
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("tokens_per_weight must be greater than zero")]
+    ZeroTokensPerWeight {},
+
+    #[error("Stake must be at least {min_bond}")]
+    BelowMinBond { min_bond: Uint128 },
+
+    #[error("Must send {expected} to stake")]
+    WrongDenom { expected: String },
+
+    #[error("No claims that can be released currently")]
+    NothingToClaim {},
+
+    #[error("{denom} is not a reward denom")]
+    UnsupportedRewardDenom { denom: String },
+
+    #[error("{denom} is already a reward denom")]
+    RewardDenomExists { denom: String },
+
+    #[error("No rewards to claim")]
+    NoRewards {},
 }
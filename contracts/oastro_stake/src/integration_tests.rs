@@ -3,6 +3,8 @@ mod tests {
     use crate::helpers::CwTemplateContract;
     use crate::msg::InstantiateMsg;
     use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use cw20::Denom;
+    use cw_utils::Duration;
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
@@ -14,9 +16,18 @@ mod tests {
         Box::new(contract)
     }
 
-    const USER: &str = "USER";
-    const ADMIN: &str = "ADMIN";
-    const NATIVE_DENOM: &str = "denom";
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const USER: &str = "user";
+    const ADMIN: &str = "admin";
+    const NATIVE_DENOM: &str = "uoastro";
 
     fn mock_app() -> App {
         AppBuilder::new().build(|router, _, storage| {
@@ -34,11 +45,34 @@ mod tests {
         })
     }
 
-    fn proper_instantiate() -> (App, CwTemplateContract) {
+    fn instantiate_oastro(app: &mut App) -> Addr {
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: "octaneAstro".to_string(),
+            symbol: "oAstro".to_string(),
+            decimals: 6,
+            initial_balances: vec![cw20::Cw20Coin { address: USER.to_string(), amount: Uint128::new(1_000) }],
+            mint: None,
+            marketing: None,
+        };
+        app.instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], "oAstro", None).unwrap()
+    }
+
+    fn instantiate_msg(denom: Denom) -> InstantiateMsg {
+        InstantiateMsg {
+            admin: ADMIN.into(),
+            denom,
+            tokens_per_weight: Uint128::new(1_000_000),
+            min_bond: Uint128::new(1),
+            unbonding_period: Duration::Time(86400),
+        }
+    }
+
+    fn proper_instantiate(denom: Denom) -> (App, CwTemplateContract) {
         let mut app = mock_app();
         let cw_template_id = app.store_code(contract_template());
 
-        let msg = InstantiateMsg { admin: "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq".into() };
+        let msg = instantiate_msg(denom);
         let cw_template_contract_addr = app
             .instantiate_contract(
                 cw_template_id,
@@ -61,11 +95,136 @@ mod tests {
 
         #[test]
         fn count() {
-            let (mut app, cw_template_contract) = proper_instantiate();
+            let (mut app, cw_template_contract) = proper_instantiate(Denom::Native(NATIVE_DENOM.to_string()));
 
             let msg = ExecuteMsg::OAstroStake{};
-            let cosmos_msg = cw_template_contract.call(msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            app.execute_contract(Addr::unchecked(USER), cw_template_contract.addr(), &msg, &[Coin { denom: NATIVE_DENOM.to_string(), amount: Uint128::new(1) }]).unwrap();
+        }
+    }
+
+    mod oastro {
+        use super::*;
+        use crate::msg::{QueryMsg, ReceiveMsg};
+        use cosmwasm_std::to_binary;
+        use cw20::{BalanceResponse, Cw20ExecuteMsg};
+        use cw4_stake::msg::StakedResponse;
+
+        #[test]
+        fn oastro_can_be_bonded() {
+            let mut app = mock_app();
+            let oastro = instantiate_oastro(&mut app);
+            let stake_id = app.store_code(contract_template());
+            let msg = InstantiateMsg { denom: Denom::Cw20(oastro.clone()), ..instantiate_msg(Denom::Native(NATIVE_DENOM.to_string())) };
+            let oastro_stake = app
+                .instantiate_contract(stake_id, Addr::unchecked(ADMIN), &msg, &[], "oastro_stake", None)
+                .unwrap();
+
+            let msg = Cw20ExecuteMsg::Send {
+                contract: oastro_stake.to_string(),
+                amount: Uint128::new(400),
+                msg: to_binary(&ReceiveMsg::Bond {}).unwrap(),
+            };
+            app.execute_contract(Addr::unchecked(USER), oastro.clone(), &msg, &[]).unwrap();
+
+            let staked: StakedResponse = app
+                .wrap()
+                .query_wasm_smart(&oastro_stake, &QueryMsg::Staked { addr: USER.to_string() })
+                .unwrap();
+            assert_eq!(staked.stake, Uint128::new(400));
+            assert_eq!(staked.denom, Denom::Cw20(oastro.clone()));
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(&oastro, &cw20::Cw20QueryMsg::Balance { address: oastro_stake.to_string() })
+                .unwrap();
+            assert_eq!(balance.balance, Uint128::new(400));
+        }
+    }
+
+    mod claims {
+        use super::*;
+        use crate::msg::{ExecuteMsg, QueryMsg};
+        use cosmwasm_std::coins;
+        use cw4::{Member, MemberListResponse, MemberResponse, TotalWeightResponse};
+        use cw_controllers::ClaimsResponse;
+
+        fn query<T: serde::de::DeserializeOwned>(app: &App, contract: &CwTemplateContract, msg: &QueryMsg) -> T {
+            app.wrap().query_wasm_smart(contract.addr(), msg).unwrap()
+        }
+
+        #[test]
+        fn unbonded_tokens_can_be_claimed_after_the_unbonding_period() {
+            let (mut app, oastro_stake) = proper_instantiate(Denom::Native(NATIVE_DENOM.to_string()));
+            app.init_modules(|router, _, storage| {
+                router.bank.init_balance(storage, &Addr::unchecked("whale"), coins(3_000_000, NATIVE_DENOM))
+            }).unwrap();
+
+            let msg = ExecuteMsg::OAstroStake {};
+            app.execute_contract(Addr::unchecked("whale"), oastro_stake.addr(), &msg, &coins(3_000_000, NATIVE_DENOM)).unwrap();
+
+            let member: MemberResponse = query(&app, &oastro_stake, &QueryMsg::Member { addr: "whale".to_string(), at_height: None });
+            assert_eq!(member.weight, Some(3));
+            let total: TotalWeightResponse = query(&app, &oastro_stake, &QueryMsg::TotalWeight {});
+            assert_eq!(total.weight, 3);
+            let members: MemberListResponse = query(&app, &oastro_stake, &QueryMsg::ListMembers { start_after: None, limit: None });
+            assert_eq!(members.members, vec![Member { addr: "whale".to_string(), weight: 3 }]);
+
+            let msg = ExecuteMsg::OAstroUnstake { amount: 1_000_000 };
+            app.execute_contract(Addr::unchecked("whale"), oastro_stake.addr(), &msg, &[]).unwrap();
+
+            let claims: ClaimsResponse = query(&app, &oastro_stake, &QueryMsg::Claims { addr: "whale".to_string() });
+            assert_eq!(claims.claims.len(), 1);
+            let total: TotalWeightResponse = query(&app, &oastro_stake, &QueryMsg::TotalWeight {});
+            assert_eq!(total.weight, 2);
+
+            // nothing is released before the unbonding period ends
+            app.execute_contract(Addr::unchecked("whale"), oastro_stake.addr(), &ExecuteMsg::Claim {}, &[]).unwrap_err();
+
+            app.update_block(|block| block.time = block.time.plus_seconds(86400));
+            app.execute_contract(Addr::unchecked("whale"), oastro_stake.addr(), &ExecuteMsg::Claim {}, &[]).unwrap();
+
+            let balance = app.wrap().query_balance("whale", NATIVE_DENOM).unwrap();
+            assert_eq!(balance.amount, Uint128::new(1_000_000));
+            let claims: ClaimsResponse = query(&app, &oastro_stake, &QueryMsg::Claims { addr: "whale".to_string() });
+            assert!(claims.claims.is_empty());
+        }
+    }
+
+    mod rewards {
+        use super::*;
+        use crate::msg::{ExecuteMsg, ReceiveMsg};
+        use cosmwasm_std::{coins, to_binary};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+        #[test]
+        fn cw20_rewards_are_paid_to_bonders() {
+            let (mut app, oastro_stake) = proper_instantiate(Denom::Native(NATIVE_DENOM.to_string()));
+            // USER holds the reward token and plays the booster here
+            let reward_token = instantiate_oastro(&mut app);
+            app.init_modules(|router, _, storage| {
+                router.bank.init_balance(storage, &Addr::unchecked("whale"), coins(1_000_000, NATIVE_DENOM))
+            }).unwrap();
+
+            let msg = ExecuteMsg::AddRewardDenom { denom: Denom::Cw20(reward_token.clone()) };
+            app.execute_contract(Addr::unchecked(ADMIN), oastro_stake.addr(), &msg, &[]).unwrap();
+
+            let msg = ExecuteMsg::OAstroStake {};
+            app.execute_contract(Addr::unchecked("whale"), oastro_stake.addr(), &msg, &coins(1_000_000, NATIVE_DENOM)).unwrap();
+
+            let msg = Cw20ExecuteMsg::Send {
+                contract: oastro_stake.addr().to_string(),
+                amount: Uint128::new(250),
+                msg: to_binary(&ReceiveMsg::DistributeRewards {}).unwrap(),
+            };
+            app.execute_contract(Addr::unchecked(USER), reward_token.clone(), &msg, &[]).unwrap();
+
+            app.execute_contract(Addr::unchecked("whale"), oastro_stake.addr(), &ExecuteMsg::ClaimRewards {}, &[]).unwrap();
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(&reward_token, &Cw20QueryMsg::Balance { address: "whale".to_string() })
+                .unwrap();
+            assert_eq!(balance.balance, Uint128::new(250));
         }
     }
 }
@@ -1,20 +1,79 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use cosmwasm_std::Uint128;
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw_utils::Duration;
+
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: String,
+    /// token that is bonded, normally the oAstro cw20 minted by astro_stake
+    pub denom: Denom,
+    pub tokens_per_weight: Uint128,
+    pub min_bond: Uint128,
+    pub unbonding_period: Duration,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     OAstroStake{},
-    OAstroUnstake{amount: u128}
+    OAstroUnstake{amount: u128},
+    /// Release unbonded tokens whose unbonding period has passed
+    Claim{},
+    /// Bond or distribute cw20 tokens, see `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
+    /// Distribute the attached native funds to bonded members by weight
+    DistributeRewards{},
+    /// Pay out every reward the sender has earned
+    ClaimRewards{},
+    /// Admin only. Existing claims keep their release time
+    UpdateConfig{tokens_per_weight: Option<Uint128>, min_bond: Option<Uint128>, unbonding_period: Option<Duration>},
+    /// Admin only. Hooks are notified of every membership change
+    AddHook{addr: String},
+    RemoveHook{addr: String},
+    /// Admin only. Rewards are only accepted in registered denoms
+    AddRewardDenom{denom: Denom},
+}
+
+/// Message to embed in a cw20 `Send` to this contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Bond{},
+    DistributeRewards{},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    Staked{addr: String}
+    Staked{addr: String},
+    Config{},
+    Claims{addr: String},
+    Member{addr: String, at_height: Option<u64>},
+    ListMembers{start_after: Option<String>, limit: Option<u32>},
+    TotalWeight{},
+    Hooks{},
+    // PendingRewards returns what the member could claim right now for every reward denom
+    PendingRewards{addr: String},
+    // RewardDenoms lists the denoms DistributeRewards accepts
+    RewardDenoms{},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAmount {
+    pub denom: Denom,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    pub rewards: Vec<RewardAmount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardDenomsResponse {
+    pub denoms: Vec<Denom>,
 }
@@ -2,4 +2,30 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
\ No newline at end of file
+use cw20::Denom;
+use cw_storage_plus::Map;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardIndex{
+    pub denom: Denom,
+    /// Rewards accumulated per unit of cw4 weight, scaled by REWARD_PRECISION
+    pub reward_per_weight: u128,
+    /// Rewards that arrived while nothing was bonded, added to the next distribution
+    pub undistributed: u128
+}
+
+/// Accepted reward denoms, keyed by `denom_key`
+pub const REWARD_INDEXES: Map<String, RewardIndex> = Map::new("reward_indexes");
+
+/// The reward_per_weight a member was last checkpointed at, keyed by (denom_key, member)
+pub const MEMBER_REWARD_PER_WEIGHT: Map<(String, Addr), u128> = Map::new("member_reward_per_weight");
+
+/// Rewards checkpointed but not yet claimed, keyed by (denom_key, member)
+pub const PENDING_REWARDS: Map<(String, Addr), u128> = Map::new("pending_rewards");
+
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(token) => format!("cw20:{}", token),
+    }
+}
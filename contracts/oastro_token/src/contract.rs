@@ -0,0 +1,76 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw2::set_contract_version;
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:oastro_token";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// oAstro is instantiated by astro_stake, which passes itself as the only minter
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let res = cw20_base::contract::instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(res.add_attribute("method", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    cw20_base::contract::execute(deps, env, info, msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    cw20_base::contract::query(deps, env, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, Uint128};
+    use cw20::{BalanceResponse, MinterResponse};
+
+    const MINTER: &str = "astro_stake";
+
+    fn get_instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            name: "octaneAstro".to_string(),
+            symbol: "oAstro".to_string(),
+            decimals: 6,
+            initial_balances: vec![],
+            mint: Some(MinterResponse { minter: MINTER.to_string(), cap: None }),
+            marketing: None,
+        }
+    }
+
+    #[test]
+    fn only_minter_mints() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        let msg = ExecuteMsg::Mint { recipient: "alice".to_string(), amount: Uint128::new(100) };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Balance { address: "alice".to_string() }).unwrap();
+        let value: BalanceResponse = from_binary(&res).unwrap();
+        assert_eq!(Uint128::new(100), value.balance);
+    }
+}
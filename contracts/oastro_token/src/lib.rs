@@ -0,0 +1,4 @@
+pub mod contract;
+pub mod msg;
+
+pub use cw20_base::ContractError;
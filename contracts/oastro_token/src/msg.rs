@@ -0,0 +1,3 @@
+//! oAstro speaks the plain cw20 spec, so its messages are the cw20-base ones.
+pub use cw20_base::msg::{InstantiateMarketingInfo, InstantiateMsg, MigrateMsg, QueryMsg};
+pub use cw20::Cw20ExecuteMsg as ExecuteMsg;
@@ -0,0 +1,587 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128, WasmMsg};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::msg::{CallbackMsg, ExecuteMsg, GeneratorExecuteMsg, GeneratorHookMsg, InstantiateMsg, PoolLengthResponse, QueryMsg, ReceiveMsg, RewardPoolHookMsg, StakerRewardDenomsResponse, StakerRewardsHookMsg, StakerRewardsQueryMsg, VotingEscrowExecuteMsg, VotingEscrowHookMsg};
+
+use cw_controllers::Admin;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom};
+
+use crate::state::{CONFIG, Config, FEES, FeeConfig, POOLS, POOL_COUNT, POOL_STAKED, PoolInfo, XASTRO_LOCKED};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:octane_booster";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const SUPER_ADMIN: &Admin = &Admin::new("SUPER_ADMIN");
+// longest lock the Astroport voting escrow accepts, two years in seconds
+const MAX_LOCK_TIME: u64 = 2 * 365 * 86400;
+const FEE_DENOMINATOR: u64 = 10_000;
+// hard caps on the fee schedule, in basis points
+const MAX_TOTAL_FEE: u64 = 2_000;
+const MAX_CALLER_FEE: u64 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
+    SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
+
+    let config = Config{
+        astro_token: deps.api.addr_validate(&msg.astro_token)?,
+        xastro_token: deps.api.addr_validate(&msg.xastro_token)?,
+        voting_escrow: deps.api.addr_validate(&msg.voting_escrow)?,
+        pool_manager: deps.api.addr_validate(&msg.pool_manager)?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    POOL_COUNT.save(deps.storage, &0)?;
+    XASTRO_LOCKED.save(deps.storage, &0)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("owner", msg.admin))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetPoolManager { pool_manager } => set_pool_manager(deps, info, pool_manager),
+        ExecuteMsg::AddPool { lp_token, guage, rewards } => add_pool(deps, info, lp_token, guage, rewards),
+        ExecuteMsg::ShutdownPool { pool_id } => shutdown_pool(deps, env, info, pool_id),
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::Withdraw { pool_id, amount, recipient } => withdraw(deps, env, info, pool_id, amount, recipient),
+        ExecuteMsg::EarmarkRewards { pool_id } => earmark_rewards(deps, env, info, pool_id),
+        ExecuteMsg::LockXAstro {} => lock_xastro(deps, env),
+        ExecuteMsg::ExtendLockTime { time } => extend_lock_time(deps, info, time),
+        ExecuteMsg::SetFees { staker_fee, caller_fee, treasury_fee, staker_rewards, treasury } => set_fees(deps, info, staker_fee, caller_fee, treasury_fee, staker_rewards, treasury),
+        ExecuteMsg::Callback(msg) => callback(deps, env, info, msg),
+    }
+}
+
+fn set_pool_manager(mut deps: DepsMut, info: MessageInfo, pool_manager: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let pool_manager_addr = deps.api.addr_validate(&pool_manager)?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.pool_manager = pool_manager_addr;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_pool_manager")
+        .add_attribute("pool_manager", pool_manager))
+}
+
+fn add_pool(deps: DepsMut, info: MessageInfo, lp_token: String, guage: String, rewards: String) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.pool_manager {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    let pool = PoolInfo{
+        lp_token: deps.api.addr_validate(&lp_token)?,
+        guage: deps.api.addr_validate(&guage)?,
+        rewards: deps.api.addr_validate(&rewards)?,
+        shutdown: false,
+    };
+
+    let exists = POOLS
+        .range(deps.storage, None, None, Order::Ascending)
+        .any(|item| matches!(item, Ok((_, existing)) if existing.lp_token == pool.lp_token && !existing.shutdown));
+    if exists {
+        return Err(ContractError::PoolExists { lp_token });
+    }
+
+    let pool_id = POOL_COUNT.load(deps.storage)?;
+    POOLS.save(deps.storage, pool_id, &pool)?;
+    POOL_COUNT.save(deps.storage, &(pool_id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_pool")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("lp_token", lp_token))
+}
+
+fn shutdown_pool(deps: DepsMut, env: Env, info: MessageInfo, pool_id: u128) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.pool_manager {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    let mut pool = POOLS.load(deps.storage, pool_id)?;
+    if pool.shutdown {
+        return Err(ContractError::PoolShutdown { pool_id });
+    }
+    pool.shutdown = true;
+    POOLS.save(deps.storage, pool_id, &pool)?;
+
+    // from here on withdrawals are paid from the LP the booster holds
+    let staked = POOL_STAKED.may_load(deps.storage, pool_id)?.unwrap_or_default();
+    POOL_STAKED.remove(deps.storage, pool_id);
+
+    let mut res = Response::new()
+        .add_attribute("method", "shutdown_pool")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("amount", staked.to_string());
+
+    if staked > 0 {
+        res = res
+            .add_message(WasmMsg::Execute {
+                contract_addr: pool.guage.to_string(),
+                msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+                    lp_token: pool.lp_token.to_string(),
+                    amount: Uint128::new(staked),
+                })?,
+                funds: vec![],
+            })
+            .add_message(distribute_rewards_msg(deps.as_ref(), &env, pool_id, None)?);
+    }
+
+    Ok(res)
+}
+
+fn receive(deps: DepsMut, env: Env, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match msg {
+        ReceiveMsg::Deposit { pool_id } => deposit(deps, env, info, sender, pool_id, wrapper.amount),
+    }
+}
+
+fn deposit(deps: DepsMut, env: Env, info: MessageInfo, sender: Addr, pool_id: u128, amount: Uint128) -> Result<Response, ContractError>{
+    let pool = POOLS.load(deps.storage, pool_id)?;
+    if pool.shutdown {
+        return Err(ContractError::PoolShutdown { pool_id });
+    }
+    // info.sender is the cw20 contract that forwarded the hook
+    if info.sender != pool.lp_token {
+        return Err(ContractError::InvalidLpToken { expected: pool.lp_token.into() });
+    }
+    // only the pool's reward contract keeps the books for staked LP
+    if sender != pool.rewards {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    POOL_STAKED.update(deps.storage, pool_id, |staked| -> StdResult<_> {
+        Ok(staked.unwrap_or_default() + amount.u128())
+    })?;
+
+    let stake_msg = WasmMsg::Execute {
+        contract_addr: pool.lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: pool.guage.to_string(),
+            amount,
+            msg: to_binary(&GeneratorHookMsg::Deposit {})?,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(stake_msg)
+        .add_message(distribute_rewards_msg(deps.as_ref(), &env, pool_id, None)?)
+        .add_attribute("method", "deposit")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+fn withdraw(deps: DepsMut, env: Env, info: MessageInfo, pool_id: u128, amount: Uint128, recipient: String) -> Result<Response, ContractError>{
+    let pool = POOLS.load(deps.storage, pool_id)?;
+    if info.sender != pool.rewards {
+        return Err(ContractError::Unauthorized {  });
+    }
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+
+    let mut res = Response::new();
+    // a shut down pool has nothing left in the generator, its LP already sits here
+    if !pool.shutdown {
+        POOL_STAKED.update(deps.storage, pool_id, |staked| -> StdResult<_> {
+            Ok(staked.unwrap_or_default().saturating_sub(amount.u128()))
+        })?;
+        res = res
+            .add_message(WasmMsg::Execute {
+                contract_addr: pool.guage.to_string(),
+                msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+                    lp_token: pool.lp_token.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })
+            .add_message(distribute_rewards_msg(deps.as_ref(), &env, pool_id, None)?);
+    }
+
+    let transfer_msg = WasmMsg::Execute {
+        contract_addr: pool.lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient_addr.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(res
+        .add_message(transfer_msg)
+        .add_attribute("method", "withdraw")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+/// Anyone can pull a pool's ASTRO out of the generator, the caller fee pays for the gas
+fn earmark_rewards(deps: DepsMut, env: Env, info: MessageInfo, pool_id: u128) -> Result<Response, ContractError>{
+    let pool = POOLS.load(deps.storage, pool_id)?;
+    if pool.shutdown {
+        return Err(ContractError::PoolShutdown { pool_id });
+    }
+
+    let claim_msg = WasmMsg::Execute {
+        contract_addr: pool.guage.to_string(),
+        msg: to_binary(&GeneratorExecuteMsg::ClaimRewards {
+            lp_tokens: vec![pool.lp_token.to_string()],
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(claim_msg)
+        .add_message(distribute_rewards_msg(deps.as_ref(), &env, pool_id, Some(info.sender))?)
+        .add_attribute("method", "earmark_rewards")
+        .add_attribute("pool_id", pool_id.to_string()))
+}
+
+/// The generator pays out ASTRO on every deposit, withdraw and claim. Snapshot the balance
+/// now so the callback can hand exactly what arrived to the pool's reward contract.
+fn distribute_rewards_msg(deps: Deps, env: &Env, pool_id: u128, caller: Option<Addr>) -> StdResult<CosmosMsg> {
+    let config = CONFIG.load(deps.storage)?;
+    let prev_balance = query_token_balance(deps, &config.astro_token, &env.contract.address)?;
+
+    Ok(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::DistributeRewards { pool_id, prev_balance, caller }))?,
+        funds: vec![],
+    }.into())
+}
+
+fn callback(deps: DepsMut, env: Env, info: MessageInfo, msg: CallbackMsg) -> Result<Response, ContractError>{
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    match msg {
+        CallbackMsg::DistributeRewards { pool_id, prev_balance, caller } => distribute_rewards(deps, env, pool_id, prev_balance, caller),
+    }
+}
+
+fn distribute_rewards(deps: DepsMut, env: Env, pool_id: u128, prev_balance: Uint128, caller: Option<Addr>) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    let pool = POOLS.load(deps.storage, pool_id)?;
+
+    let balance = query_token_balance(deps.as_ref(), &config.astro_token, &env.contract.address)?;
+    let harvested = balance.saturating_sub(prev_balance);
+
+    let mut res = Response::new()
+        .add_attribute("method", "distribute_rewards")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("amount", harvested);
+
+    if harvested.is_zero() {
+        return Ok(res);
+    }
+
+    let mut remaining = harvested;
+    if let Some(fees) = FEES.may_load(deps.storage)? {
+        // oastro_stake rejects denoms it doesn't track, the staker share then stays with the pool
+        let mut staker_amount = harvested.multiply_ratio(fees.staker_fee, FEE_DENOMINATOR);
+        if !staker_amount.is_zero() && !staker_accepts_astro(deps.as_ref(), &config, &fees) {
+            res = res.add_attribute("staker_fee_redirected", staker_amount);
+            staker_amount = Uint128::zero();
+        }
+        let treasury_amount = harvested.multiply_ratio(fees.treasury_fee, FEE_DENOMINATOR);
+        // without an explicit caller the caller share stays with the pool
+        let caller_amount = match caller {
+            Some(_) => harvested.multiply_ratio(fees.caller_fee, FEE_DENOMINATOR),
+            None => Uint128::zero(),
+        };
+        remaining = harvested - staker_amount - treasury_amount - caller_amount;
+
+        if !staker_amount.is_zero() {
+            res = res.add_message(astro_msg(&config, Cw20ExecuteMsg::Send {
+                contract: fees.staker_rewards.to_string(),
+                amount: staker_amount,
+                msg: to_binary(&StakerRewardsHookMsg::DistributeRewards {})?,
+            })?);
+        }
+        if !treasury_amount.is_zero() {
+            res = res.add_message(astro_msg(&config, Cw20ExecuteMsg::Transfer { recipient: fees.treasury.to_string(), amount: treasury_amount })?);
+        }
+        if let Some(caller) = caller.filter(|_| !caller_amount.is_zero()) {
+            res = res.add_message(astro_msg(&config, Cw20ExecuteMsg::Transfer { recipient: caller.to_string(), amount: caller_amount })?);
+        }
+        res = res
+            .add_attribute("staker_fee", staker_amount)
+            .add_attribute("treasury_fee", treasury_amount)
+            .add_attribute("caller_fee", caller_amount);
+    }
+
+    if !remaining.is_zero() {
+        // the reward pool streams what it is sent over its reward duration
+        res = res.add_message(astro_msg(&config, Cw20ExecuteMsg::Send {
+            contract: pool.rewards.to_string(),
+            amount: remaining,
+            msg: to_binary(&RewardPoolHookMsg::QueueNewRewards {})?,
+        })?);
+    }
+
+    Ok(res)
+}
+
+/// A staker_rewards contract that can't answer the query is treated as not accepting ASTRO,
+/// so a misconfigured fee never blocks deposits and withdrawals. SetFees checks the query
+/// answers, and distribute_rewards reports the redirected share.
+fn staker_accepts_astro(deps: Deps, config: &Config, fees: &FeeConfig) -> bool {
+    deps.querier
+        .query_wasm_smart::<StakerRewardDenomsResponse>(&fees.staker_rewards, &StakerRewardsQueryMsg::RewardDenoms {})
+        .map(|res| res.denoms.contains(&Denom::Cw20(config.astro_token.clone())))
+        .unwrap_or(false)
+}
+
+fn astro_msg(config: &Config, msg: Cw20ExecuteMsg) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.astro_token.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    })
+}
+
+fn set_fees(mut deps: DepsMut, info: MessageInfo, staker_fee: u64, caller_fee: u64, treasury_fee: u64, staker_rewards: String, treasury: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    if caller_fee > MAX_CALLER_FEE {
+        return Err(ContractError::FeeTooHigh { max: MAX_CALLER_FEE });
+    }
+    if staker_fee.saturating_add(caller_fee).saturating_add(treasury_fee) > MAX_TOTAL_FEE {
+        return Err(ContractError::FeeTooHigh { max: MAX_TOTAL_FEE });
+    }
+
+    let fees = FeeConfig{
+        staker_fee,
+        caller_fee,
+        treasury_fee,
+        staker_rewards: deps.api.addr_validate(&staker_rewards)?,
+        treasury: deps.api.addr_validate(&treasury)?,
+    };
+    // catch a mistyped or undeployed staker_rewards here rather than on every harvest
+    if staker_fee > 0 {
+        deps.querier
+            .query_wasm_smart::<StakerRewardDenomsResponse>(&fees.staker_rewards, &StakerRewardsQueryMsg::RewardDenoms {})
+            .map_err(|_| ContractError::InvalidStakerRewards { staker_rewards: staker_rewards.clone() })?;
+    }
+    FEES.save(deps.storage, &fees)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_fees")
+        .add_attribute("staker_fee", staker_fee.to_string())
+        .add_attribute("caller_fee", caller_fee.to_string())
+        .add_attribute("treasury_fee", treasury_fee.to_string()))
+}
+
+fn lock_xastro(deps: DepsMut, env: Env) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    let amount = query_token_balance(deps.as_ref(), &config.xastro_token, &env.contract.address)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToLock {  });
+    }
+
+    let locked = XASTRO_LOCKED.load(deps.storage)?;
+    let hook = if locked == 0 {
+        VotingEscrowHookMsg::CreateLock { time: MAX_LOCK_TIME }
+    } else {
+        VotingEscrowHookMsg::ExtendLockAmount {}
+    };
+    XASTRO_LOCKED.save(deps.storage, &(locked + amount.u128()))?;
+
+    let lock_msg = WasmMsg::Execute {
+        contract_addr: config.xastro_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: config.voting_escrow.to_string(),
+            amount,
+            msg: to_binary(&hook)?,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(lock_msg)
+        .add_attribute("method", "lock_xastro")
+        .add_attribute("amount", amount))
+}
+
+fn extend_lock_time(mut deps: DepsMut, info: MessageInfo, time: u64) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let extend_msg = WasmMsg::Execute {
+        contract_addr: config.voting_escrow.to_string(),
+        msg: to_binary(&VotingEscrowExecuteMsg::ExtendLockTime { time })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(extend_msg)
+        .add_attribute("method", "extend_lock_time")
+        .add_attribute("time", time.to_string()))
+}
+
+fn query_token_balance(deps: Deps, token: &Addr, account: &Addr) -> StdResult<Uint128> {
+    let res: BalanceResponse = deps.querier.query_wasm_smart(token, &Cw20QueryMsg::Balance { address: account.to_string() })?;
+    Ok(res.balance)
+}
+
+fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
+    let super_admin = SUPER_ADMIN.assert_admin(deps.as_ref(), &info.sender);
+
+    match super_admin {
+        Ok(()) => Ok(()),
+        Err(_not_admin) => Err(ContractError::Unauthorized {  })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Pool { pool_id } => to_binary(&POOLS.load(deps.storage, pool_id)?),
+        QueryMsg::PoolLength {} => to_binary(&PoolLengthResponse { length: POOL_COUNT.load(deps.storage)? }),
+        QueryMsg::Fees {} => to_binary(&FEES.may_load(deps.storage)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, ContractResult, SystemResult};
+
+    const ADMIN: &str = "admin";
+    const POOL_MANAGER: &str = "pool_manager";
+
+    fn get_instantiate_msg() -> InstantiateMsg{
+        InstantiateMsg {
+            admin: ADMIN.to_string(),
+            astro_token: "astro".to_string(),
+            xastro_token: "xastro".to_string(),
+            voting_escrow: "voting_escrow".to_string(),
+            pool_manager: POOL_MANAGER.to_string(),
+        }
+    }
+
+    fn add_pool_msg(lp_token: &str) -> ExecuteMsg {
+        ExecuteMsg::AddPool { lp_token: lp_token.to_string(), guage: "generator".to_string(), rewards: format!("{}_rewards", lp_token) }
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PoolLength {}).unwrap();
+        let value: PoolLengthResponse = from_binary(&res).unwrap();
+        assert_eq!(0, value.length);
+    }
+
+    #[test]
+    fn add_pool() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        // only the pool manager registers pools
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_pool_msg("lp_one")).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        execute(deps.as_mut(), mock_env(), mock_info(POOL_MANAGER, &[]), add_pool_msg("lp_one")).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info(POOL_MANAGER, &[]), add_pool_msg("lp_two")).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(POOL_MANAGER, &[]), add_pool_msg("lp_one")).unwrap_err();
+        assert!(matches!(err, ContractError::PoolExists { .. }));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Pool { pool_id: 1 }).unwrap();
+        let pool: PoolInfo = from_binary(&res).unwrap();
+        assert_eq!(pool.lp_token, Addr::unchecked("lp_two"));
+        assert_eq!(pool.rewards, Addr::unchecked("lp_two_rewards"));
+        assert!(!pool.shutdown);
+    }
+
+    #[test]
+    fn set_pool_manager() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        let msg = ExecuteMsg::SetPoolManager { pool_manager: "registry".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(POOL_MANAGER, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+        execute(deps.as_mut(), mock_env(), mock_info("registry", &[]), add_pool_msg("lp_one")).unwrap();
+    }
+
+    #[test]
+    fn callback_only_from_self() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info(POOL_MANAGER, &[]), add_pool_msg("lp_one")).unwrap();
+
+        let msg = ExecuteMsg::Callback(CallbackMsg::DistributeRewards { pool_id: 0, prev_balance: Uint128::zero(), caller: None });
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn fees_are_capped() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        let set_fees = |staker_fee: u64, caller_fee: u64, treasury_fee: u64| ExecuteMsg::SetFees {
+            staker_fee,
+            caller_fee,
+            treasury_fee,
+            staker_rewards: "oastro_stake".to_string(),
+            treasury: "treasury".to_string(),
+        };
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(POOL_MANAGER, &[]), set_fees(1_000, 100, 500)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), set_fees(1_000, 101, 500)).unwrap_err();
+        assert!(matches!(err, ContractError::FeeTooHigh { max: MAX_CALLER_FEE }));
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), set_fees(1_500, 100, 401)).unwrap_err();
+        assert!(matches!(err, ContractError::FeeTooHigh { max: MAX_TOTAL_FEE }));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+        let fees: Option<FeeConfig> = from_binary(&res).unwrap();
+        assert_eq!(fees, None);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), set_fees(1_500, 100, 400)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidStakerRewards { .. }));
+
+        deps.querier.update_wasm(|_| SystemResult::Ok(ContractResult::Ok(to_binary(&StakerRewardDenomsResponse { denoms: vec![] }).unwrap())));
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), set_fees(1_500, 100, 400)).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+        let fees: Option<FeeConfig> = from_binary(&res).unwrap();
+        assert_eq!(fees.unwrap().staker_fee, 1_500);
+    }
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Pool {pool_id} is shut down")]
+    PoolShutdown { pool_id: u128 },
+
+    #[error("A pool for LP token {lp_token} already exists")]
+    PoolExists { lp_token: String },
+
+    #[error("Invalid LP token, expected {expected}")]
+    InvalidLpToken { expected: String },
+
+    #[error("No xASTRO to lock")]
+    NothingToLock {},
+
+    #[error("Fees exceed the {max} basis point cap")]
+    FeeTooHigh { max: u64 },
+
+    #[error("{staker_rewards} does not answer the staker RewardDenoms query")]
+    InvalidStakerRewards { staker_rewards: String },
+}
@@ -0,0 +1,670 @@
+#[cfg(test)]
+mod tests {
+    use crate::helpers::CwTemplateContract;
+    use crate::msg::InstantiateMsg;
+    use cosmwasm_std::{Addr, Empty, Uint128};
+    use cw20::{Cw20Coin, Denom, MinterResponse};
+    use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+
+    pub fn contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_reward_pool() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            octane_reward_pool::contract::execute,
+            octane_reward_pool::contract::instantiate,
+            octane_reward_pool::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const ADMIN: &str = "admin";
+    const ALICE: &str = "alice";
+    const BOB: &str = "bob";
+
+    /// Stands in for the Astroport generator: stakes LP per (lp_token, user) and pays out
+    /// every ASTRO it holds to whoever touches their position.
+    mod mock_generator {
+        use cosmwasm_std::{
+            from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+            Response, StdResult, Uint128, WasmMsg,
+        };
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::{Item, Map};
+        use serde::{Deserialize, Serialize};
+
+        use crate::msg::GeneratorHookMsg;
+
+        const ASTRO: Item<Addr> = Item::new("astro");
+        const DEPOSITS: Map<(Addr, Addr), Uint128> = Map::new("deposits");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        pub struct InstantiateMsg {
+            pub astro_token: String,
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            Receive(Cw20ReceiveMsg),
+            ClaimRewards { lp_tokens: Vec<String> },
+            Withdraw { lp_token: String, amount: Uint128 },
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum QueryMsg {
+            Deposit { lp_token: String, user: String },
+        }
+
+        fn pay_rewards(deps: Deps, env: &Env, user: &Addr) -> StdResult<Vec<CosmosMsg>> {
+            let astro = ASTRO.load(deps.storage)?;
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                &astro,
+                &Cw20QueryMsg::Balance { address: env.contract.address.to_string() },
+            )?;
+            if res.balance.is_zero() {
+                return Ok(vec![]);
+            }
+            Ok(vec![WasmMsg::Execute {
+                contract_addr: astro.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: user.to_string(), amount: res.balance })?,
+                funds: vec![],
+            }
+            .into()])
+        }
+
+        fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            match msg {
+                ExecuteMsg::Receive(wrapper) => {
+                    let GeneratorHookMsg::Deposit {} = from_binary(&wrapper.msg)?;
+                    let user = Addr::unchecked(&wrapper.sender);
+                    DEPOSITS.update(deps.storage, (info.sender, user.clone()), |d| -> StdResult<_> {
+                        Ok(d.unwrap_or_default() + wrapper.amount)
+                    })?;
+                    Ok(Response::new().add_messages(pay_rewards(deps.as_ref(), &env, &user)?))
+                }
+                ExecuteMsg::ClaimRewards { .. } => {
+                    Ok(Response::new().add_messages(pay_rewards(deps.as_ref(), &env, &info.sender)?))
+                }
+                ExecuteMsg::Withdraw { lp_token, amount } => {
+                    let lp_token = Addr::unchecked(lp_token);
+                    DEPOSITS.update(deps.storage, (lp_token.clone(), info.sender.clone()), |d| -> StdResult<_> {
+                        Ok(d.unwrap_or_default().checked_sub(amount)?)
+                    })?;
+                    Ok(Response::new()
+                        .add_message(WasmMsg::Execute {
+                            contract_addr: lp_token.to_string(),
+                            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: info.sender.to_string(), amount })?,
+                            funds: vec![],
+                        })
+                        .add_messages(pay_rewards(deps.as_ref(), &env, &info.sender)?))
+                }
+            }
+        }
+
+        fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+            ASTRO.save(deps.storage, &Addr::unchecked(msg.astro_token))?;
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+            match msg {
+                QueryMsg::Deposit { lp_token, user } => to_binary(
+                    &DEPOSITS
+                        .may_load(deps.storage, (Addr::unchecked(lp_token), Addr::unchecked(user)))?
+                        .unwrap_or_default(),
+                ),
+            }
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    /// Stands in for the Astroport voting escrow, remembering the last lock action
+    mod mock_voting_escrow {
+        use cosmwasm_std::{
+            from_binary, to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+            Uint128,
+        };
+        use cw20::Cw20ReceiveMsg;
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::Item;
+        use serde::{Deserialize, Serialize};
+
+        use crate::msg::VotingEscrowHookMsg;
+
+        const LOCKED: Item<Uint128> = Item::new("locked");
+        const LAST_ACTION: Item<String> = Item::new("last_action");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            Receive(Cw20ReceiveMsg),
+            ExtendLockTime { time: u64 },
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        pub struct LockResponse {
+            pub locked: Uint128,
+            pub last_action: String,
+        }
+
+        fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            let action = match msg {
+                ExecuteMsg::Receive(wrapper) => {
+                    let locked = LOCKED.may_load(deps.storage)?.unwrap_or_default();
+                    LOCKED.save(deps.storage, &(locked + wrapper.amount))?;
+                    match from_binary(&wrapper.msg)? {
+                        VotingEscrowHookMsg::CreateLock { .. } => "create_lock",
+                        VotingEscrowHookMsg::ExtendLockAmount {} => "extend_lock_amount",
+                    }
+                }
+                ExecuteMsg::ExtendLockTime { .. } => "extend_lock_time",
+            };
+            LAST_ACTION.save(deps.storage, &action.to_string())?;
+            Ok(Response::new())
+        }
+
+        fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&LockResponse {
+                locked: LOCKED.may_load(deps.storage)?.unwrap_or_default(),
+                last_action: LAST_ACTION.may_load(deps.storage)?.unwrap_or_default(),
+            })
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    /// Stands in for oastro_stake: takes whatever is sent with the DistributeRewards hook
+    /// and reports the reward denoms it was instantiated with
+    mod mock_staker_rewards {
+        use cosmwasm_std::{from_binary, to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+        use cw20::{Cw20ReceiveMsg, Denom};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::Item;
+        use serde::{Deserialize, Serialize};
+
+        use crate::msg::{StakerRewardDenomsResponse, StakerRewardsHookMsg, StakerRewardsQueryMsg};
+
+        const DENOMS: Item<Vec<Denom>> = Item::new("denoms");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        pub struct InstantiateMsg {
+            pub denoms: Vec<Denom>,
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            Receive(Cw20ReceiveMsg),
+        }
+
+        fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            let ExecuteMsg::Receive(wrapper) = msg;
+            let StakerRewardsHookMsg::DistributeRewards {} = from_binary(&wrapper.msg)?;
+            Ok(Response::new())
+        }
+
+        fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+            DENOMS.save(deps.storage, &msg.denoms)?;
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, msg: StakerRewardsQueryMsg) -> StdResult<Binary> {
+            let StakerRewardsQueryMsg::RewardDenoms {} = msg;
+            to_binary(&StakerRewardDenomsResponse { denoms: DENOMS.load(deps.storage)? })
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    fn mock_app() -> App {
+        AppBuilder::new().build(|_, _, _| {})
+    }
+
+    fn instantiate_cw20(app: &mut App, symbol: &str, holders: &[&str]) -> Addr {
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(1_000_000),
+                })
+                .collect(),
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        };
+        app.instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], symbol, None)
+            .unwrap()
+    }
+
+    struct Suite {
+        app: App,
+        booster: CwTemplateContract,
+        reward_pool: Addr,
+        generator: Addr,
+        voting_escrow: Addr,
+        astro: Addr,
+        xastro: Addr,
+        lp: Addr,
+    }
+
+    fn proper_instantiate() -> Suite {
+        let mut app = mock_app();
+        let astro = instantiate_cw20(&mut app, "ASTRO", &[ADMIN]);
+        let xastro = instantiate_cw20(&mut app, "XASTRO", &[ADMIN]);
+        let lp = instantiate_cw20(&mut app, "LPTOKEN", &[ALICE, BOB]);
+
+        let generator_id = app.store_code(mock_generator::contract());
+        let generator = app
+            .instantiate_contract(
+                generator_id,
+                Addr::unchecked(ADMIN),
+                &mock_generator::InstantiateMsg { astro_token: astro.to_string() },
+                &[],
+                "generator",
+                None,
+            )
+            .unwrap();
+
+        let voting_escrow_id = app.store_code(mock_voting_escrow::contract());
+        let voting_escrow = app
+            .instantiate_contract(voting_escrow_id, Addr::unchecked(ADMIN), &Empty {}, &[], "vxastro", None)
+            .unwrap();
+
+        let cw_template_id = app.store_code(contract_template());
+        let msg = InstantiateMsg {
+            admin: ADMIN.to_string(),
+            astro_token: astro.to_string(),
+            xastro_token: xastro.to_string(),
+            voting_escrow: voting_escrow.to_string(),
+            pool_manager: ADMIN.to_string(),
+        };
+        let cw_template_contract_addr = app
+            .instantiate_contract(cw_template_id, Addr::unchecked(ADMIN), &msg, &[], "test", None)
+            .unwrap();
+        let booster = CwTemplateContract(cw_template_contract_addr);
+
+        let reward_pool_id = app.store_code(contract_reward_pool());
+        let reward_pool = app
+            .instantiate_contract(
+                reward_pool_id,
+                Addr::unchecked(ADMIN),
+                &octane_reward_pool::msg::InstantiateMsg { admin: ADMIN.to_string() },
+                &[],
+                "rewards",
+                None,
+            )
+            .unwrap();
+
+        let msg = crate::msg::ExecuteMsg::AddPool {
+            lp_token: lp.to_string(),
+            guage: generator.to_string(),
+            rewards: reward_pool.to_string(),
+        };
+        app.execute(Addr::unchecked(ADMIN), booster.call(msg).unwrap())
+            .unwrap();
+
+        let msg = octane_reward_pool::msg::ExecuteMsg::Init {
+            astro_token: astro.to_string(),
+            astro_guage: generator.to_string(),
+            octane_staker: "staker".to_string(),
+            octane_booster: booster.addr().to_string(),
+            lp_token: lp.to_string(),
+            oastro_token: "oastro".to_string(),
+            pool_id: 0,
+            reward_manager: ADMIN.to_string(),
+        };
+        app.execute_contract(Addr::unchecked(ADMIN), reward_pool.clone(), &msg, &[])
+            .unwrap();
+
+        Suite { app, booster, reward_pool, generator, voting_escrow, astro, xastro, lp }
+    }
+
+    mod booster {
+        use super::*;
+        use crate::msg::ExecuteMsg;
+        use cosmwasm_std::to_binary;
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+        use octane_reward_pool::msg::{EarnedResponse, QueryMsg as RewardQueryMsg, ReceiveMsg as RewardReceiveMsg};
+
+        impl Suite {
+            fn deposit(&mut self, account: &str, amount: u128) {
+                let msg = Cw20ExecuteMsg::Send {
+                    contract: self.reward_pool.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&RewardReceiveMsg::Deposit {}).unwrap(),
+                };
+                self.app
+                    .execute_contract(Addr::unchecked(account), self.lp.clone(), &msg, &[])
+                    .unwrap();
+            }
+
+            fn withdraw(&mut self, account: &str, amount: u128) {
+                let msg = octane_reward_pool::msg::ExecuteMsg::Withdraw { amount, claim: false };
+                self.app
+                    .execute_contract(Addr::unchecked(account), self.reward_pool.clone(), &msg, &[])
+                    .unwrap();
+            }
+
+            fn transfer(&mut self, token: &Addr, recipient: &Addr, amount: u128) {
+                let msg = Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::new(amount),
+                };
+                self.app
+                    .execute_contract(Addr::unchecked(ADMIN), token.clone(), &msg, &[])
+                    .unwrap();
+            }
+
+            fn earmark(&mut self, caller: &str) -> AppResponse {
+                self.app
+                    .execute(Addr::unchecked(caller), self.booster.call(ExecuteMsg::EarmarkRewards { pool_id: 0 }).unwrap())
+                    .unwrap()
+            }
+
+            fn balance(&self, token: &Addr, account: &Addr) -> u128 {
+                let res: BalanceResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: account.to_string() })
+                    .unwrap();
+                res.balance.u128()
+            }
+
+            fn generator_deposit(&self) -> Uint128 {
+                self.app
+                    .wrap()
+                    .query_wasm_smart(
+                        &self.generator,
+                        &mock_generator::QueryMsg::Deposit {
+                            lp_token: self.lp.to_string(),
+                            user: self.booster.addr().to_string(),
+                        },
+                    )
+                    .unwrap()
+            }
+
+            /// Lets the reward pool stream out everything queued so far
+            fn stream(&mut self) {
+                self.app.update_block(|block| block.time = block.time.plus_seconds(7 * 86400));
+            }
+
+            fn earned(&self, account: &str) -> u128 {
+                let res: EarnedResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(&self.reward_pool, &RewardQueryMsg::Earned { account: account.to_string() })
+                    .unwrap();
+                res.rewards[0].amount
+            }
+        }
+
+        #[test]
+        fn deposits_are_staked_in_generator() {
+            let mut suite = proper_instantiate();
+
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 50);
+            assert_eq!(suite.generator_deposit(), Uint128::new(150));
+            assert_eq!(suite.balance(&suite.lp, &suite.booster.addr()), 0);
+
+            suite.withdraw(ALICE, 40);
+            assert_eq!(suite.generator_deposit(), Uint128::new(110));
+            assert_eq!(suite.balance(&suite.lp, &Addr::unchecked(ALICE)), 1_000_000 - 60);
+        }
+
+        #[test]
+        fn earmark_moves_astro_into_reward_pool() {
+            let mut suite = proper_instantiate();
+
+            suite.deposit(ALICE, 100);
+            let (astro, generator) = (suite.astro.clone(), suite.generator.clone());
+            suite.transfer(&astro, &generator, 1_000);
+            suite.earmark(ADMIN);
+
+            assert_eq!(suite.balance(&astro, &suite.reward_pool), 1_000);
+            assert_eq!(suite.balance(&astro, &suite.booster.addr()), 0);
+            suite.stream();
+            assert_eq!(suite.earned(ALICE), 1_000);
+
+            // earmarking is permissionless so keepers can run it
+            suite.deposit(BOB, 100);
+            suite.transfer(&astro, &generator, 500);
+            suite.earmark("keeper");
+            suite.stream();
+            assert_eq!(suite.earned(ALICE), 1_250);
+            assert_eq!(suite.earned(BOB), 250);
+        }
+
+        #[test]
+        fn earmark_takes_fees_before_the_reward_pool() {
+            let mut suite = proper_instantiate();
+            let staker_id = suite.app.store_code(mock_staker_rewards::contract());
+            let msg = mock_staker_rewards::InstantiateMsg { denoms: vec![Denom::Cw20(suite.astro.clone())] };
+            let staker = suite
+                .app
+                .instantiate_contract(staker_id, Addr::unchecked(ADMIN), &msg, &[], "oastro_stake", None)
+                .unwrap();
+
+            let msg = ExecuteMsg::SetFees {
+                staker_fee: 1_000,
+                caller_fee: 100,
+                treasury_fee: 500,
+                staker_rewards: staker.to_string(),
+                treasury: "treasury".to_string(),
+            };
+            suite.app.execute(Addr::unchecked(ADMIN), suite.booster.call(msg).unwrap()).unwrap();
+
+            suite.deposit(ALICE, 100);
+            let (astro, generator) = (suite.astro.clone(), suite.generator.clone());
+            suite.transfer(&astro, &generator, 1_000);
+            suite.earmark("keeper");
+
+            assert_eq!(suite.balance(&astro, &staker), 100);
+            assert_eq!(suite.balance(&astro, &Addr::unchecked("treasury")), 50);
+            assert_eq!(suite.balance(&astro, &Addr::unchecked("keeper")), 10);
+            assert_eq!(suite.balance(&astro, &suite.reward_pool), 840);
+
+            // rewards paid out on deposit have no caller, its share stays with the pool
+            suite.transfer(&astro, &generator, 1_000);
+            suite.deposit(BOB, 100);
+            assert_eq!(suite.balance(&astro, &staker), 200);
+            assert_eq!(suite.balance(&astro, &Addr::unchecked("treasury")), 100);
+            assert_eq!(suite.balance(&astro, &suite.reward_pool), 840 + 850);
+        }
+
+        #[test]
+        fn staker_fee_stays_with_pool_until_astro_is_a_staker_reward() {
+            let mut suite = proper_instantiate();
+            let staker_id = suite.app.store_code(mock_staker_rewards::contract());
+            let msg = mock_staker_rewards::InstantiateMsg { denoms: vec![] };
+            let staker = suite
+                .app
+                .instantiate_contract(staker_id, Addr::unchecked(ADMIN), &msg, &[], "oastro_stake", None)
+                .unwrap();
+
+            // an address that isn't a staker rewards contract is caught when the fees are set
+            let set_fees = |staker_rewards: &Addr| ExecuteMsg::SetFees {
+                staker_fee: 1_000,
+                caller_fee: 100,
+                treasury_fee: 500,
+                staker_rewards: staker_rewards.to_string(),
+                treasury: "treasury".to_string(),
+            };
+            let err = suite
+                .app
+                .execute(Addr::unchecked(ADMIN), suite.booster.call(set_fees(&Addr::unchecked("treasury"))).unwrap())
+                .unwrap_err();
+            assert_eq!(err.root_cause().to_string(), "treasury does not answer the staker RewardDenoms query");
+            suite.app.execute(Addr::unchecked(ADMIN), suite.booster.call(set_fees(&staker)).unwrap()).unwrap();
+
+            suite.deposit(ALICE, 100);
+            let (astro, generator) = (suite.astro.clone(), suite.generator.clone());
+            suite.transfer(&astro, &generator, 1_000);
+            let res = suite.earmark("keeper");
+            assert!(res.events.iter().flat_map(|event| &event.attributes).any(|attr| attr.key == "staker_fee_redirected" && attr.value == "100"));
+
+            assert_eq!(suite.balance(&astro, &staker), 0);
+            assert_eq!(suite.balance(&astro, &Addr::unchecked("treasury")), 50);
+            assert_eq!(suite.balance(&astro, &Addr::unchecked("keeper")), 10);
+            assert_eq!(suite.balance(&astro, &suite.reward_pool), 940);
+
+            // deposits and withdrawals that harvest keep working
+            suite.transfer(&astro, &generator, 1_000);
+            suite.deposit(BOB, 100);
+            suite.withdraw(BOB, 100);
+            assert_eq!(suite.balance(&astro, &staker), 0);
+        }
+
+        #[test]
+        fn rewards_paid_on_deposit_reach_reward_pool() {
+            let mut suite = proper_instantiate();
+
+            suite.deposit(ALICE, 100);
+            let (astro, generator) = (suite.astro.clone(), suite.generator.clone());
+            suite.transfer(&astro, &generator, 300);
+
+            // the generator pays pending ASTRO whenever the position changes
+            suite.deposit(ALICE, 100);
+            assert_eq!(suite.balance(&astro, &suite.booster.addr()), 0);
+            assert_eq!(suite.balance(&astro, &suite.reward_pool), 300);
+        }
+
+        #[test]
+        fn only_reward_pool_moves_lp() {
+            let mut suite = proper_instantiate();
+            suite.deposit(ALICE, 100);
+
+            // LP sent straight to the booster is not credited anywhere, so it is refused
+            let msg = Cw20ExecuteMsg::Send {
+                contract: suite.booster.addr().to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&crate::msg::ReceiveMsg::Deposit { pool_id: 0 }).unwrap(),
+            };
+            suite
+                .app
+                .execute_contract(Addr::unchecked(BOB), suite.lp.clone(), &msg, &[])
+                .unwrap_err();
+
+            let msg = ExecuteMsg::Withdraw { pool_id: 0, amount: Uint128::new(100), recipient: ALICE.to_string() };
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.booster.call(msg).unwrap())
+                .unwrap_err();
+        }
+
+        #[test]
+        fn lock_xastro() {
+            let mut suite = proper_instantiate();
+            let (xastro, booster) = (suite.xastro.clone(), suite.booster.addr());
+
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.booster.call(ExecuteMsg::LockXAstro {}).unwrap())
+                .unwrap_err();
+
+            suite.transfer(&xastro, &booster, 700);
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.booster.call(ExecuteMsg::LockXAstro {}).unwrap())
+                .unwrap();
+
+            let res: mock_voting_escrow::LockResponse =
+                suite.app.wrap().query_wasm_smart(&suite.voting_escrow, &Empty {}).unwrap();
+            assert_eq!(res.locked, Uint128::new(700));
+            assert_eq!(res.last_action, "create_lock");
+
+            suite.transfer(&xastro, &booster, 300);
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.booster.call(ExecuteMsg::LockXAstro {}).unwrap())
+                .unwrap();
+
+            let res: mock_voting_escrow::LockResponse =
+                suite.app.wrap().query_wasm_smart(&suite.voting_escrow, &Empty {}).unwrap();
+            assert_eq!(res.locked, Uint128::new(1_000));
+            assert_eq!(res.last_action, "extend_lock_amount");
+            assert_eq!(suite.balance(&xastro, &booster), 0);
+        }
+
+        #[test]
+        fn shutdown_pool_exits_generator_and_pays_withdrawals_from_booster() {
+            let mut suite = proper_instantiate();
+
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 100);
+            let (astro, generator) = (suite.astro.clone(), suite.generator.clone());
+            suite.transfer(&astro, &generator, 600);
+
+            // only the pool manager retires pools
+            let msg = ExecuteMsg::ShutdownPool { pool_id: 0 };
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.booster.call(msg.clone()).unwrap())
+                .unwrap_err();
+            suite
+                .app
+                .execute(Addr::unchecked(ADMIN), suite.booster.call(msg.clone()).unwrap())
+                .unwrap();
+            suite
+                .app
+                .execute_contract(Addr::unchecked(ADMIN), suite.reward_pool.clone(), &octane_reward_pool::msg::ExecuteMsg::Shutdown {}, &[])
+                .unwrap();
+
+            // all LP came back and the last generator payout reached the reward pool
+            assert_eq!(suite.generator_deposit(), Uint128::zero());
+            assert_eq!(suite.balance(&suite.lp, &suite.booster.addr()), 200);
+            assert_eq!(suite.balance(&astro, &suite.reward_pool), 600);
+            assert_eq!(suite.earned(ALICE), 300);
+
+            suite
+                .app
+                .execute(Addr::unchecked(ADMIN), suite.booster.call(msg).unwrap())
+                .unwrap_err();
+            suite
+                .app
+                .execute(Addr::unchecked(ADMIN), suite.booster.call(ExecuteMsg::EarmarkRewards { pool_id: 0 }).unwrap())
+                .unwrap_err();
+
+            suite.withdraw(ALICE, 100);
+            assert_eq!(suite.balance(&suite.lp, &Addr::unchecked(ALICE)), 1_000_000);
+            assert_eq!(suite.balance(&suite.lp, &suite.booster.addr()), 100);
+            assert_eq!(suite.earned(BOB), 300);
+        }
+    }
+}
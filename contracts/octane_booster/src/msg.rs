@@ -0,0 +1,115 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw20::{Cw20ReceiveMsg, Denom};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub astro_token: String,
+    pub xastro_token: String,
+    pub voting_escrow: String,
+    pub pool_manager: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    SetPoolManager{pool_manager: String},
+    AddPool{lp_token: String, guage: String, rewards: String},
+    // ShutdownPool pulls the pool's LP out of the generator and keeps it here for withdrawal
+    ShutdownPool{pool_id: u128},
+    // Receive takes LP tokens forwarded by a pool's reward contract
+    Receive(Cw20ReceiveMsg),
+    Withdraw{pool_id: u128, amount: Uint128, recipient: String},
+    // EarmarkRewards claims a pool's ASTRO from the generator, takes fees and sends the rest to its reward pool
+    EarmarkRewards{pool_id: u128},
+    LockXAstro{},
+    ExtendLockTime{time: u64},
+    // SetFees replaces the fee schedule, fees are in basis points
+    SetFees{staker_fee: u64, caller_fee: u64, treasury_fee: u64, staker_rewards: String, treasury: String},
+    Callback(CallbackMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Deposit{pool_id: u128},
+}
+
+/// Messages the booster sends to itself once a generator call has settled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    // caller is paid the caller fee, it is only set by EarmarkRewards
+    DistributeRewards{pool_id: u128, prev_balance: Uint128, caller: Option<Addr>},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Pool {pool_id: u128},
+    PoolLength {},
+    Fees {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolLengthResponse {
+    pub length: u128,
+}
+
+/// Hook understood by the Astroport generator when LP is sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorHookMsg {
+    Deposit{},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorExecuteMsg {
+    ClaimRewards{lp_tokens: Vec<String>},
+    Withdraw{lp_token: String, amount: Uint128},
+}
+
+/// Hooks understood by the Astroport voting escrow when xASTRO is sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingEscrowHookMsg {
+    CreateLock{time: u64},
+    ExtendLockAmount{},
+}
+
+/// Hook understood by a pool's reward contract when ASTRO is sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardPoolHookMsg {
+    QueueNewRewards{},
+}
+
+/// Hook understood by oastro_stake when reward tokens are sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakerRewardsHookMsg {
+    DistributeRewards{},
+}
+
+/// oastro_stake query for the denoms its DistributeRewards hook accepts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakerRewardsQueryMsg {
+    RewardDenoms{},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerRewardDenomsResponse {
+    pub denoms: Vec<Denom>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingEscrowExecuteMsg {
+    ExtendLockTime{time: u64},
+}
@@ -0,0 +1,47 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config{
+    pub astro_token: Addr,
+    pub xastro_token: Addr,
+    pub voting_escrow: Addr,
+    pub pool_manager: Addr
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Cut of every harvested ASTRO reward, in basis points
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeConfig{
+    pub staker_fee: u64,
+    pub caller_fee: u64,
+    pub treasury_fee: u64,
+    /// oastro_stake, paid through its cw20 DistributeRewards hook
+    pub staker_rewards: Addr,
+    pub treasury: Addr
+}
+
+/// Not set until the admin configures fees, harvests go to the pools in full until then
+pub const FEES: Item<FeeConfig> = Item::new("fees");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub lp_token: Addr,
+    pub guage: Addr,
+    pub rewards: Addr,
+    pub shutdown: bool,
+}
+
+pub const POOLS: Map<u128, PoolInfo> = Map::new("pools");
+
+/// LP the booster has staked in the generator for each pool
+pub const POOL_STAKED: Map<u128, u128> = Map::new("pool_staked");
+
+pub const POOL_COUNT: Item<u128> = Item::new("pool_count");
+
+/// xASTRO the booster has moved into its vxASTRO lock so far
+pub const XASTRO_LOCKED: Item<u128> = Item::new("xastro_locked");
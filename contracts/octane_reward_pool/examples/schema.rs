@@ -3,7 +3,7 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use octane_reward_pool::msg::{TokenAmountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use octane_reward_pool::msg::{TokenAmountResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RewardPerTokenResponse, RewardTokensResponse};
 use octane_reward_pool::state::{Config};
 
 fn main() {
@@ -17,4 +17,6 @@ fn main() {
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(Config), &out_dir);
     export_schema(&schema_for!(TokenAmountResponse), &out_dir);
+    export_schema(&schema_for!(RewardPerTokenResponse), &out_dir);
+    export_schema(&schema_for!(RewardTokensResponse), &out_dir);
 }
@@ -1,20 +1,21 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{ Uint128, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{ Uint128, coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg};
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{TokenAmountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{TokenAmountResponse, EarnedData, EarnedResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, BoosterExecuteMsg, BoosterReceiveMsg, RewardPerTokenData, RewardPerTokenResponse, RewardTokenData, RewardTokensResponse};
 
 use cw_controllers::Admin;
-use cw20_base::contract::{execute_mint, query_balance};
-use cw20_base::state::{TOKEN_INFO, TokenInfo, MinterData};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
 
-use crate::state::{CONFIG, Config, RewardType};
-use crate::state::{REWARD_MAP, REWARDS};
+use crate::state::{CONFIG, Config, RewardType, Rewards};
+use crate::state::{REWARD_MAP, REWARDS, REWARD_INTEGRAL_FOR, BALANCES, TOTAL_SUPPLY, denom_key};
 
 const MAX_REWARDS:u128 = 8;
-const INJ_ADDRESS: &str = "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq";
+// reward_integral is scaled by this factor to keep precision on small deltas
+const REWARD_PRECISION: u128 = 100_000_000_000_000_000_000;
+const DEFAULT_REWARD_DURATION: u64 = 7 * 86400;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:octane_reward_pool";
@@ -32,15 +33,8 @@ pub fn instantiate(
     let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
     SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
     
-    let token_info = TokenInfo{
-        name: "octaneAstro".to_string(),
-        symbol: "oAstro".to_string(),
-        decimals: 6,
-        total_supply: Uint128::zero(),
-        mint: Some(MinterData { minter: _env.contract.address, cap: None })
-    };
-
-    TOKEN_INFO.save(deps.storage, &token_info)?;
+    REWARDS.save(deps.storage, &Rewards{ items: vec![] })?;
+    TOTAL_SUPPLY.save(deps.storage, &0)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     
@@ -59,13 +53,25 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Init { astro_token, astro_guage, octane_staker, octane_booster, lp_token, pool_id } => initialize(deps, info, astro_token, astro_guage, octane_staker, octane_booster, lp_token, pool_id),
-        ExecuteMsg::GetReward{account, forward_to} => get_reward(deps, info, account, forward_to),
+        ExecuteMsg::Init { astro_token, astro_guage, octane_staker, octane_booster, lp_token, oastro_token, pool_id, reward_manager } => initialize(deps, info, astro_token, astro_guage, octane_staker, octane_booster, lp_token, oastro_token, pool_id, reward_manager),
+        ExecuteMsg::GetReward{account, forward_to} => get_reward(deps, env, info, account, forward_to),
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::Withdraw{amount, claim} => withdraw(deps, env, info, amount, claim),
+        ExecuteMsg::Shutdown{} => shutdown(deps, env, info),
+        ExecuteMsg::SetRewardDuration{duration} => set_reward_duration(deps, info, duration),
+        ExecuteMsg::SetRewardManager{reward_manager} => set_reward_manager(deps, info, reward_manager),
+        ExecuteMsg::AddRewardToken{token} => add_reward_token(deps, info, token),
+        ExecuteMsg::RemoveRewardToken{token} => remove_reward_token(deps, info, token),
+        ExecuteMsg::QueueNewRewards{} => {
+            let queued = info.funds.iter().map(|coin| (Denom::Native(coin.denom.clone()), coin.amount.u128())).collect();
+            queue_new_rewards(deps, env, info.sender, queued)
+        },
     }
 }
 
-fn initialize(mut deps: DepsMut, info: MessageInfo, astro_token: String, astro_guage: String, octane_staker:String, octane_booster: String, lp_token: String, pool_id: u128) -> Result<Response, ContractError>{
-    // check_admin(deps.branch(), info)?;
+#[allow(clippy::too_many_arguments)]
+fn initialize(mut deps: DepsMut, info: MessageInfo, astro_token: String, astro_guage: String, octane_staker:String, octane_booster: String, lp_token: String, oastro_token: String, pool_id: u128, reward_manager: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
 
     let config_to_store = Config{
         astro_token: deps.api.addr_validate(&astro_token)?,
@@ -73,51 +79,429 @@ fn initialize(mut deps: DepsMut, info: MessageInfo, astro_token: String, astro_g
         octane_staker: deps.api.addr_validate(&octane_staker)?,
         octane_booster: deps.api.addr_validate(&octane_booster)?,
         lp_token: deps.api.addr_validate(&lp_token)?,
-        pool_id
+        oastro_token: deps.api.addr_validate(&oastro_token)?,
+        pool_id,
+        shutdown: false,
+        reward_duration: DEFAULT_REWARD_DURATION,
+        reward_manager: deps.api.addr_validate(&reward_manager)?
     };
 
+    insert_reward_token(deps.branch(), Denom::Cw20(config_to_store.astro_token.clone()))?;
     CONFIG.save(deps.storage, &config_to_store)?;
 
-    insert_reward_token(deps, INJ_ADDRESS.into())?;
-
 
     Ok(Response::new())
 }
 
-fn insert_reward_token(deps: DepsMut, reward_token: String) -> Result<Response, ContractError>{
-    let reward_token_addr = deps.api.addr_validate(&reward_token)?;
-    REWARDS.update(deps.storage, |mut rewards| -> Result<_, ContractError>{
-        if rewards.items.len() < MAX_REWARDS as usize{
+fn add_reward_token(deps: DepsMut, info: MessageInfo, token: Denom) -> Result<Response, ContractError>{
+    check_reward_manager(deps.as_ref(), &info.sender)?;
+
+    let token = match token {
+        Denom::Cw20(addr) => Denom::Cw20(deps.api.addr_validate(addr.as_str())?),
+        native => native,
+    };
+    insert_reward_token(deps, token.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_reward_token")
+        .add_attribute("token", token_label(&token)))
+}
+
+/// Adding a deactivated token back reactivates it with its integral intact. Deactivated
+/// tokens keep their slot since accounts may still have rewards to claim from them.
+fn insert_reward_token(deps: DepsMut, token: Denom) -> Result<(), ContractError>{
+    let mut rewards = REWARDS.load(deps.storage)?;
+
+    match rewards.items.iter_mut().find(|reward| reward.reward_token == token) {
+        Some(reward) if reward.active => return Err(ContractError::RewardTokenExists { token: token_label(&token) }),
+        Some(reward) => reward.active = true,
+        None => {
+            if rewards.items.len() >= MAX_REWARDS as usize {
+                return Err(ContractError::MaxRewardsReached { max: MAX_REWARDS });
+            }
             rewards.items.push(RewardType{
-                reward_token: reward_token_addr,
+                reward_token: token,
+                active: true,
                 reward_integral: 0,
-                reward_remaining: 0
+                reward_remaining: 0,
+                period_finish: 0,
+                last_update_time: 0
             });
         }
+    }
+
+    REWARDS.save(deps.storage, &rewards)?;
+    Ok(())
+}
+
+fn remove_reward_token(deps: DepsMut, info: MessageInfo, token: Denom) -> Result<Response, ContractError>{
+    check_reward_manager(deps.as_ref(), &info.sender)?;
+
+    // the booster queues every harvest as ASTRO, a deactivated ASTRO would revert deposits and withdrawals
+    let config = CONFIG.load(deps.storage)?;
+    if token == Denom::Cw20(config.astro_token) {
+        return Err(ContractError::ProtectedRewardToken { token: token_label(&token) });
+    }
+
+    REWARDS.update(deps.storage, |mut rewards| -> Result<_, ContractError> {
+        let reward = rewards.items
+            .iter_mut()
+            .find(|reward| reward.reward_token == token && reward.active)
+            .ok_or(ContractError::InvalidRewardToken { token: token_label(&token) })?;
+        reward.active = false;
         Ok(rewards)
     })?;
 
-    Ok(Response::new().add_attribute("added reward token", reward_token))
+    Ok(Response::new()
+        .add_attribute("method", "remove_reward_token")
+        .add_attribute("token", token_label(&token)))
+}
 
+fn receive(deps: DepsMut, env: Env, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match msg {
+        ReceiveMsg::Deposit{} => deposit(deps, env, info, sender, wrapper.amount.u128()),
+        // info.sender is the reward token that forwarded the hook
+        ReceiveMsg::QueueNewRewards{} => queue_new_rewards(deps, env, sender, vec![(Denom::Cw20(info.sender), wrapper.amount.u128())]),
+    }
 }
 
-fn get_reward(deps: DepsMut, info: MessageInfo, account: String, forward_to: String) -> Result<Response, ContractError>{
-    checkpoint(deps, info, account, forward_to);
-    Ok(Response::new())
+/// Adds to the rewards still being streamed and restarts the period, so whatever was
+/// left plus the new amount is paid out evenly over the next reward_duration.
+fn queue_new_rewards(mut deps: DepsMut, env: Env, sender: Addr, queued: Vec<(Denom, u128)>) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if config.shutdown {
+        return Err(ContractError::PoolShutdown { pool_id: config.pool_id });
+    }
+    if sender != config.octane_booster {
+        check_reward_manager(deps.as_ref(), &sender)?;
+    }
+    if queued.is_empty() || queued.iter().any(|(_, amount)| *amount == 0) {
+        return Err(ContractError::ZeroAmount {  });
+    }
+
+    checkpoint(deps.branch(), &env, &[])?;
+
+    let now = env.block.time.seconds();
+    let period_finish = now + config.reward_duration;
+    let mut rewards = REWARDS.load(deps.storage)?;
+    let mut res = Response::new()
+        .add_attribute("method", "queue_new_rewards")
+        .add_attribute("period_finish", period_finish.to_string());
+
+    for (token, amount) in queued {
+        let reward = rewards.items
+            .iter_mut()
+            .find(|reward| reward.reward_token == token && reward.active)
+            .ok_or(ContractError::InvalidRewardToken { token: token_label(&token) })?;
+
+        reward.reward_remaining += amount;
+        reward.last_update_time = now;
+        reward.period_finish = period_finish;
+
+        res = res
+            .add_attribute("token", token_label(&token))
+            .add_attribute("amount", amount.to_string());
+    }
+    REWARDS.save(deps.storage, &rewards)?;
+
+    Ok(res)
 }
 
-fn checkpoint(deps: DepsMut, info: MessageInfo, account: String, forward_to: String){
-    update_and_claim_rewards(deps, info);
+fn set_reward_duration(deps: DepsMut, info: MessageInfo, duration: u64) -> Result<Response, ContractError>{
+    check_reward_manager(deps.as_ref(), &info.sender)?;
+    if duration == 0 {
+        return Err(ContractError::ZeroDuration {  });
+    }
+
+    // running periods keep their finish time, the new duration applies from the next queue
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.reward_duration = duration;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_reward_duration")
+        .add_attribute("duration", duration.to_string()))
 }
 
-fn update_and_claim_rewards(deps: DepsMut, info: MessageInfo) {
-    //TODO: Check if the pool is shutdown
-    update_rewards_list(deps, info);
+fn set_reward_manager(deps: DepsMut, info: MessageInfo, reward_manager: String) -> Result<Response, ContractError>{
+    check_reward_manager(deps.as_ref(), &info.sender)?;
+
+    let reward_manager_addr = deps.api.addr_validate(&reward_manager)?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.reward_manager = reward_manager_addr;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_reward_manager")
+        .add_attribute("reward_manager", reward_manager))
 }
 
-fn update_rewards_list(deps: DepsMut, info: MessageInfo){
-    for i in 0..MAX_REWARDS {
-        //TODO: Astroport hasn't implemented Gauges
+fn deposit(mut deps: DepsMut, env: Env, info: MessageInfo, account: Addr, amount: u128) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    // info.sender is the cw20 contract that forwarded the hook
+    if info.sender != config.lp_token {
+        return Err(ContractError::InvalidLpToken { expected: config.lp_token.into() });
+    }
+    if config.shutdown {
+        return Err(ContractError::PoolShutdown { pool_id: config.pool_id });
+    }
+    if amount == 0 {
+        return Err(ContractError::ZeroAmount {  });
+    }
+
+    checkpoint(deps.branch(), &env, &[&account])?;
+
+    BALANCES.update(deps.storage, account.clone(), |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + amount)
+    })?;
+    TOTAL_SUPPLY.update(deps.storage, |supply| -> StdResult<_> { Ok(supply + amount) })?;
+
+    let stake_msg = WasmMsg::Execute {
+        contract_addr: config.lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: config.octane_booster.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&BoosterReceiveMsg::Deposit { pool_id: config.pool_id })?,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(stake_msg)
+        .add_attribute("method", "deposit")
+        .add_attribute("account", account)
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn withdraw(mut deps: DepsMut, env: Env, info: MessageInfo, amount: u128, claim: bool) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if amount == 0 {
+        return Err(ContractError::ZeroAmount {  });
+    }
+
+    let claim_msgs = if claim {
+        checkpoint_and_claim(deps.branch(), &env, &info.sender, &info.sender)?
+    } else {
+        checkpoint(deps.branch(), &env, &[&info.sender])?;
+        vec![]
+    };
+
+    let available = BALANCES.may_load(deps.storage, info.sender.clone())?.unwrap_or_default();
+    if amount > available {
+        return Err(ContractError::InsufficientBalance { requested: amount, available });
+    }
+    BALANCES.save(deps.storage, info.sender.clone(), &(available - amount))?;
+    TOTAL_SUPPLY.update(deps.storage, |supply| -> StdResult<_> { Ok(supply - amount) })?;
+
+    let unstake_msg = WasmMsg::Execute {
+        contract_addr: config.octane_booster.to_string(),
+        msg: to_binary(&BoosterExecuteMsg::Withdraw {
+            pool_id: config.pool_id,
+            amount: Uint128::new(amount),
+            recipient: info.sender.to_string(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_messages(claim_msgs)
+        .add_message(unstake_msg)
+        .add_attribute("method", "withdraw")
+        .add_attribute("account", info.sender)
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn shutdown(mut deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.shutdown {
+        return Err(ContractError::PoolShutdown { pool_id: config.pool_id });
+    }
+
+    // end every period now so the checkpoint pays out everything still queued, including
+    // whatever the booster harvested on the way out, before freezing the integrals
+    let now = env.block.time.seconds();
+    REWARDS.update(deps.storage, |mut rewards| -> StdResult<_> {
+        for reward in rewards.items.iter_mut() {
+            reward.period_finish = reward.period_finish.min(now);
+        }
+        Ok(rewards)
+    })?;
+    checkpoint(deps.branch(), &env, &[])?;
+    config.shutdown = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "shutdown")
+        .add_attribute("pool_id", config.pool_id.to_string());
+
+    // with nothing staked the checkpoint couldn't stream anything, and the frozen integrals
+    // never will, so what is still queued goes back to the reward manager
+    if TOTAL_SUPPLY.load(deps.storage)? == 0 {
+        let mut rewards = REWARDS.load(deps.storage)?;
+        for reward in rewards.items.iter_mut().filter(|reward| reward.reward_remaining > 0) {
+            res = res
+                .add_message(reward_transfer_msg(&reward.reward_token, &config.reward_manager, reward.reward_remaining)?)
+                .add_attribute("returned_token", token_label(&reward.reward_token))
+                .add_attribute("returned_amount", reward.reward_remaining.to_string());
+            reward.reward_remaining = 0;
+        }
+        REWARDS.save(deps.storage, &rewards)?;
+    }
+
+    Ok(res)
+}
+
+fn get_reward(deps: DepsMut, env: Env, info: MessageInfo, account: String, forward_to: String) -> Result<Response, ContractError>{
+    let account_addr = deps.api.addr_validate(&account)?;
+    let forward_to_addr = deps.api.addr_validate(&forward_to)?;
+
+    // anyone may trigger a claim, but only the account itself can send its rewards elsewhere
+    if forward_to_addr != account_addr && info.sender != account_addr {
+        return Err(ContractError::Unauthorized {  });
+    }
+
+    let msgs = checkpoint_and_claim(deps, &env, &account_addr, &forward_to_addr)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("method", "get_reward")
+        .add_attribute("account", account)
+        .add_attribute("forward_to", forward_to))
+}
+
+/// Brings the reward integrals and the given accounts up to date.
+/// Must be called before any change to an account balance or the total supply.
+fn checkpoint(deps: DepsMut, env: &Env, accounts: &[&Addr]) -> Result<(), ContractError>{
+    update_and_claim_rewards(deps, env, accounts, None)?;
+    Ok(())
+}
+
+fn checkpoint_and_claim(deps: DepsMut, env: &Env, account: &Addr, forward_to: &Addr) -> Result<Vec<CosmosMsg>, ContractError>{
+    update_and_claim_rewards(deps, env, &[account], Some(forward_to))
+}
+
+fn update_and_claim_rewards(deps: DepsMut, env: &Env, accounts: &[&Addr], claim_to: Option<&Addr>) -> Result<Vec<CosmosMsg>, ContractError> {
+    let shutdown = CONFIG.load(deps.storage)?.shutdown;
+    let supply = TOTAL_SUPPLY.load(deps.storage)?;
+    let balances = accounts
+        .iter()
+        .map(|account| Ok(BALANCES.may_load(deps.storage, (*account).clone())?.unwrap_or_default()))
+        .collect::<StdResult<Vec<u128>>>()?;
+
+    update_rewards_list(deps, env, accounts, &balances, supply, claim_to, shutdown)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_rewards_list(mut deps: DepsMut, env: &Env, accounts: &[&Addr], balances: &[u128], supply: u128, claim_to: Option<&Addr>, shutdown: bool) -> Result<Vec<CosmosMsg>, ContractError>{
+    let mut rewards = REWARDS.load(deps.storage)?;
+    let mut msgs = vec![];
+
+    for reward in rewards.items.iter_mut() {
+        if let Some(msg) = calc_reward_integral(deps.branch(), env, reward, accounts, balances, supply, claim_to, shutdown)? {
+            msgs.push(msg);
+        }
+    }
+
+    REWARDS.save(deps.storage, &rewards)?;
+    Ok(msgs)
+}
+
+/// Streams what the current period has released since the last checkpoint over the current
+/// supply, then settles each account against the new integral. When `claim_to` is set the
+/// settled amount is paid out instead of being stored as claimable. Once the pool is
+/// shut down the integral no longer moves and accounts only settle what they had accrued.
+#[allow(clippy::too_many_arguments)]
+fn calc_reward_integral(deps: DepsMut, env: &Env, reward: &mut RewardType, accounts: &[&Addr], balances: &[u128], supply: u128, claim_to: Option<&Addr>, shutdown: bool) -> Result<Option<CosmosMsg>, ContractError>{
+    if !shutdown {
+        stream_rewards(reward, env.block.time.seconds(), supply);
+    }
+
+    let mut transfer = None;
+    for (account, balance) in accounts.iter().zip(balances) {
+        let key = (denom_key(&reward.reward_token), (*account).clone());
+        let user_integral = REWARD_INTEGRAL_FOR.may_load(deps.storage, key.clone())?.unwrap_or_default();
+        if claim_to.is_none() && user_integral >= reward.reward_integral {
+            continue;
+        }
+
+        let claimable = REWARD_MAP.may_load(deps.storage, key.clone())?.unwrap_or_default()
+            + earned_since(*balance, user_integral, reward.reward_integral);
+
+        match claim_to {
+            Some(recipient) if claimable > 0 => {
+                REWARD_MAP.remove(deps.storage, key.clone());
+                transfer = Some(reward_transfer_msg(&reward.reward_token, recipient, claimable)?);
+            },
+            _ => REWARD_MAP.save(deps.storage, key.clone(), &claimable)?,
+        }
+        REWARD_INTEGRAL_FOR.save(deps.storage, key, &reward.reward_integral)?;
+    }
+
+    Ok(transfer)
+}
+
+fn reward_transfer_msg(token: &Denom, recipient: &Addr, amount: u128) -> StdResult<CosmosMsg> {
+    Ok(match token {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount, denom),
+        }.into(),
+        Denom::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) })?,
+            funds: vec![],
+        }.into(),
+    })
+}
+
+/// Nothing streams while the pool is empty. The period is paused instead, pushing
+/// period_finish back by the idle time, so the first depositor can't collect at once
+/// what would have been released to nobody.
+fn stream_rewards(reward: &mut RewardType, now: u64, supply: u128) {
+    if supply == 0 && reward.reward_remaining > 0 {
+        if now > reward.last_update_time {
+            reward.period_finish += now - reward.last_update_time;
+            reward.last_update_time = now;
+        }
+        return;
+    }
+
+    let streamed = streamed_since_update(reward, now);
+    if streamed > 0 {
+        reward.reward_integral += Uint128::new(streamed).multiply_ratio(REWARD_PRECISION, supply).u128();
+        reward.reward_remaining -= streamed;
+    }
+    reward.last_update_time = reward.last_update_time.max(now.min(reward.period_finish));
+}
+
+/// Share of reward_remaining released between last_update_time and now. Streaming the
+/// remainder rather than a fixed rate * elapsed pays out exactly what was queued.
+fn streamed_since_update(reward: &RewardType, now: u64) -> u128 {
+    if now >= reward.period_finish {
+        return reward.reward_remaining;
+    }
+    if now <= reward.last_update_time {
+        return 0;
+    }
+    Uint128::new(reward.reward_remaining)
+        .multiply_ratio(now - reward.last_update_time, reward.period_finish - reward.last_update_time)
+        .u128()
+}
+
+fn earned_since(balance: u128, from_integral: u128, to_integral: u128) -> u128 {
+    Uint128::new(balance).multiply_ratio(to_integral - from_integral, REWARD_PRECISION).u128()
+}
+
+fn token_label(token: &Denom) -> String {
+    match token {
+        Denom::Native(denom) => denom.clone(),
+        Denom::Cw20(token) => token.to_string(),
     }
 }
 
@@ -126,27 +510,97 @@ fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
     
     match super_admin {
         Ok(()) => Ok(()),
-        Err(_not_admin) => return Err(ContractError::Unauthorized {  })
+        Err(_not_admin) => Err(ContractError::Unauthorized {  })
     }
 }
 
+/// The admin is the astro_stake contract, which has no messages for the reward schedule,
+/// so the reward manager it names at Init is who tunes it in practice.
+fn check_reward_manager(deps: Deps, sender: &Addr) -> Result<(), ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    if *sender == config.reward_manager || SUPER_ADMIN.is_admin(deps, sender)? {
+        return Ok(());
+    }
+    Err(ContractError::Unauthorized {  })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetTokenAmount {addr} => to_binary(&query_token(deps, addr)?),
+        QueryMsg::Earned {account} => to_binary(&query_earned(deps, env, account)?),
+        QueryMsg::StakedBalance {account} => to_binary(&query_staked_balance(deps, account)?),
+        QueryMsg::TotalSupply {} => to_binary(&TokenAmountResponse { amount: TOTAL_SUPPLY.load(deps.storage)? }),
+        QueryMsg::RewardPerToken {} => to_binary(&query_reward_per_token(deps, env)?),
+        QueryMsg::RewardTokens {} => to_binary(&query_reward_tokens(deps)?),
+    }
+}
+
+/// The rewards list as the next checkpoint would leave it
+fn current_rewards(deps: Deps, env: &Env) -> StdResult<Vec<RewardType>> {
+    let shutdown = CONFIG.load(deps.storage)?.shutdown;
+    let supply = TOTAL_SUPPLY.load(deps.storage)?;
+
+    let mut rewards = REWARDS.load(deps.storage)?.items;
+    if !shutdown {
+        for reward in rewards.iter_mut() {
+            stream_rewards(reward, env.block.time.seconds(), supply);
+        }
     }
+    Ok(rewards)
+}
+
+fn query_reward_per_token(deps: Deps, env: Env) -> StdResult<RewardPerTokenResponse> {
+    let rewards = current_rewards(deps, &env)?
+        .into_iter()
+        .map(|reward| RewardPerTokenData { token: reward.reward_token, reward_per_token: reward.reward_integral })
+        .collect();
+
+    Ok(RewardPerTokenResponse { rewards })
+}
+
+fn query_reward_tokens(deps: Deps) -> StdResult<RewardTokensResponse> {
+    let tokens = REWARDS.load(deps.storage)?
+        .items
+        .into_iter()
+        .map(|reward| RewardTokenData { token: reward.reward_token, active: reward.active, period_finish: reward.period_finish })
+        .collect();
+
+    Ok(RewardTokensResponse { tokens })
+}
+
+fn query_staked_balance(deps: Deps, account: String) -> StdResult<TokenAmountResponse> {
+    let account_addr = deps.api.addr_validate(&account)?;
+    let amount = BALANCES.may_load(deps.storage, account_addr)?.unwrap_or_default();
+    Ok(TokenAmountResponse { amount })
 }
 
-fn query_token(deps: Deps, addr: String) -> StdResult<TokenAmountResponse> {
-    let res = query_balance(deps, addr)?;
-    Ok(TokenAmountResponse { amount: res.balance.u128() })    
+fn query_earned(deps: Deps, env: Env, account: String) -> StdResult<EarnedResponse> {
+    let account_addr = deps.api.addr_validate(&account)?;
+    let balance = BALANCES.may_load(deps.storage, account_addr.clone())?.unwrap_or_default();
+
+    let rewards = current_rewards(deps, &env)?
+        .into_iter()
+        .map(|reward| {
+            let integral = reward.reward_integral;
+            let key = (denom_key(&reward.reward_token), account_addr.clone());
+            let user_integral = REWARD_INTEGRAL_FOR.may_load(deps.storage, key.clone())?.unwrap_or_default();
+            let claimable = REWARD_MAP.may_load(deps.storage, key)?.unwrap_or_default();
+
+            Ok(EarnedData {
+                amount: claimable + earned_since(balance, user_integral, integral),
+                token: reward.reward_token,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(EarnedResponse { rewards })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+    use cosmwasm_std::coins;
 
     const ASTRO_ADDRESS: &str  = "astro";
     
@@ -176,13 +630,9 @@ mod tests {
         let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
 
-        let info = mock_info(&"inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq".to_string(), &coins(5, ASTRO_ADDRESS));
-        let msg = ExecuteMsg::Init { astro_token: ASTRO_ADDRESS.to_owned(), astro_guage: ASTRO_ADDRESS.to_owned(), octane_staker:ASTRO_ADDRESS.to_owned(), octane_booster:ASTRO_ADDRESS.to_owned(), lp_token:ASTRO_ADDRESS.to_owned(), pool_id: 1};
+        let info = mock_info("inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq", &coins(5, ASTRO_ADDRESS));
+        let msg = ExecuteMsg::Init { astro_token: ASTRO_ADDRESS.to_owned(), astro_guage: ASTRO_ADDRESS.to_owned(), octane_staker:ASTRO_ADDRESS.to_owned(), octane_booster:ASTRO_ADDRESS.to_owned(), lp_token:ASTRO_ADDRESS.to_owned(), oastro_token:ASTRO_ADDRESS.to_owned(), pool_id: 1, reward_manager: ASTRO_ADDRESS.to_owned()};
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    //    let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTokenAmount { addr: "anyone".to_string() }).unwrap();
-    //     let value: TokenAmountResponse = from_binary(&res).unwrap();
-    //     assert_eq!(5, value.amount); 
         
         
     }
@@ -9,8 +9,30 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("Custom Error val: {val:?}")]
-    CustomError { val: String },
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("Invalid LP token, expected {expected}")]
+    InvalidLpToken { expected: String },
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Reward duration must be greater than zero")]
+    ZeroDuration {},
+
+    #[error("{token} is not a reward token")]
+    InvalidRewardToken { token: String },
+
+    #[error("{token} is already a reward token")]
+    RewardTokenExists { token: String },
+
+    #[error("{token} is harvested by the booster and cannot be removed")]
+    ProtectedRewardToken { token: String },
+
+    #[error("Cannot add more than {max} reward tokens")]
+    MaxRewardsReached { max: u128 },
+
+    #[error("Cannot withdraw {requested}, only {available} staked")]
+    InsufficientBalance { requested: u128, available: u128 },
+
+    #[error("Pool {pool_id} is shut down")]
+    PoolShutdown { pool_id: u128 },
 }
@@ -27,14 +27,14 @@ impl CwTemplateContract {
         .into())
     }
 
-    /// Get Count
-    pub fn count<Q, T, CQ>(&self, querier: &Q) -> StdResult<TokenAmountResponse>
+    /// Get StakedBalance
+    pub fn staked_balance<Q, T, CQ>(&self, querier: &Q, account: T) -> StdResult<TokenAmountResponse>
     where
         Q: Querier,
         T: Into<String>,
         CQ: CustomQuery,
     {
-        let msg = QueryMsg::GetTokenAmount { addr: "inj".to_string() };
+        let msg = QueryMsg::StakedBalance { account: account.into() };
         let query = WasmQuery::Smart {
             contract_addr: self.addr().into(),
             msg: to_binary(&msg)?,
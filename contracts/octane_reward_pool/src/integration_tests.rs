@@ -3,6 +3,7 @@ mod tests {
     use crate::helpers::CwTemplateContract;
     use crate::msg::InstantiateMsg;
     use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
@@ -14,10 +15,72 @@ mod tests {
         Box::new(contract)
     }
 
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
     const USER: &str = "USER";
-    const ADMIN: &str = "ADMIN";
+    const ADMIN: &str = "admin";
     const NATIVE_DENOM: &str = "denom";
 
+    /// Stands in for the octane booster: holds forwarded LP and releases it on Withdraw
+    mod mock_booster {
+        use cosmwasm_std::{
+            from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+            StdResult, Uint128, WasmMsg,
+        };
+        use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::Item;
+        use serde::{Deserialize, Serialize};
+
+        use crate::msg::BoosterReceiveMsg;
+
+        const LP_TOKEN: Item<Addr> = Item::new("lp_token");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            Receive(Cw20ReceiveMsg),
+            Withdraw { pool_id: u128, amount: Uint128, recipient: String },
+        }
+
+        fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            match msg {
+                ExecuteMsg::Receive(wrapper) => {
+                    let BoosterReceiveMsg::Deposit { .. } = from_binary(&wrapper.msg)?;
+                    LP_TOKEN.save(deps.storage, &info.sender)?;
+                    Ok(Response::new())
+                }
+                ExecuteMsg::Withdraw { amount, recipient, .. } => {
+                    let lp_token = LP_TOKEN.load(deps.storage)?;
+                    Ok(Response::new().add_message(WasmMsg::Execute {
+                        contract_addr: lp_token.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+                        funds: vec![],
+                    }))
+                }
+            }
+        }
+
+        fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&Empty {})
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
     fn mock_app() -> App {
         AppBuilder::new().build(|router, _, storage| {
             router
@@ -31,6 +94,17 @@ mod tests {
                     }],
                 )
                 .unwrap();
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(ADMIN),
+                    vec![Coin {
+                        denom: NATIVE_DENOM.to_string(),
+                        amount: Uint128::new(1_000_000),
+                    }],
+                )
+                .unwrap();
         })
     }
 
@@ -38,7 +112,7 @@ mod tests {
         let mut app = mock_app();
         let cw_template_id = app.store_code(contract_template());
 
-        let msg = InstantiateMsg { admin: "inj16wx7ye3ce060tjvmmpu8lm0ak5xr7gm2e0qwcq".into() };
+        let msg = InstantiateMsg { admin: ADMIN.into() };
         let cw_template_contract_addr = app
             .instantiate_contract(
                 cw_template_id,
@@ -55,17 +129,614 @@ mod tests {
         (app, cw_template_contract)
     }
 
-    mod count {
+    fn instantiate_cw20(app: &mut App, symbol: &str, holders: &[&str]) -> Addr {
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(1_000_000),
+                })
+                .collect(),
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        };
+        app.instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], symbol, None)
+            .unwrap()
+    }
+
+    mod rewards {
         use super::*;
-        use crate::msg::ExecuteMsg;
+        use crate::msg::{EarnedResponse, ExecuteMsg, QueryMsg, ReceiveMsg, RewardPerTokenResponse, RewardTokensResponse, TokenAmountResponse};
+        use cosmwasm_std::{coins, to_binary, Empty};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Denom};
+
+        const ALICE: &str = "alice";
+        const BOB: &str = "bob";
+        const CAROL: &str = "carol";
+        const MANAGER: &str = "manager";
+        const WEEK: u64 = 7 * 86400;
+
+        struct Suite {
+            app: App,
+            pool: CwTemplateContract,
+            astro: Addr,
+            lp: Addr,
+            booster: Addr,
+        }
+
+        fn setup() -> Suite {
+            let (mut app, pool) = proper_instantiate();
+            let astro = instantiate_cw20(&mut app, "ASTRO", &[ADMIN]);
+            let lp = instantiate_cw20(&mut app, "LPTOKEN", &[ALICE, BOB, CAROL]);
+
+            let booster_id = app.store_code(mock_booster::contract());
+            let booster = app
+                .instantiate_contract(booster_id, Addr::unchecked(ADMIN), &Empty {}, &[], "booster", None)
+                .unwrap();
+
+            let msg = ExecuteMsg::Init {
+                astro_token: astro.to_string(),
+                astro_guage: "guage".to_string(),
+                octane_staker: "staker".to_string(),
+                octane_booster: booster.to_string(),
+                lp_token: lp.to_string(),
+                oastro_token: "oastro".to_string(),
+                pool_id: 0,
+                reward_manager: MANAGER.to_string(),
+            };
+            app.execute(Addr::unchecked(ADMIN), pool.call(msg).unwrap())
+                .unwrap();
+
+            Suite { app, pool, astro, lp, booster }
+        }
+
+        impl Suite {
+            fn deposit(&mut self, account: &str, amount: u128) {
+                let msg = Cw20ExecuteMsg::Send {
+                    contract: self.pool.addr().to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&ReceiveMsg::Deposit {}).unwrap(),
+                };
+                self.app
+                    .execute_contract(Addr::unchecked(account), self.lp.clone(), &msg, &[])
+                    .unwrap();
+            }
+
+            fn withdraw(&mut self, account: &str, amount: u128, claim: bool) {
+                let msg = ExecuteMsg::Withdraw { amount, claim };
+                self.app
+                    .execute(Addr::unchecked(account), self.pool.call(msg).unwrap())
+                    .unwrap();
+            }
+
+            fn queue(&mut self, sender: &str, amount: u128) -> Result<(), String> {
+                let msg = Cw20ExecuteMsg::Send {
+                    contract: self.pool.addr().to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&ReceiveMsg::QueueNewRewards {}).unwrap(),
+                };
+                self.app
+                    .execute_contract(Addr::unchecked(sender), self.astro.clone(), &msg, &[])
+                    .map(|_| ())
+                    .map_err(|err| err.root_cause().to_string())
+            }
+
+            fn execute(&mut self, sender: &str, msg: ExecuteMsg, funds: &[Coin]) -> Result<(), String> {
+                self.app
+                    .execute_contract(Addr::unchecked(sender), self.pool.addr(), &msg, funds)
+                    .map(|_| ())
+                    .map_err(|err| err.root_cause().to_string())
+            }
+
+            fn reward_tokens(&self) -> Vec<(Denom, bool)> {
+                let res: RewardTokensResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(self.pool.addr(), &QueryMsg::RewardTokens {})
+                    .unwrap();
+                res.tokens.into_iter().map(|token| (token.token, token.active)).collect()
+            }
+
+            fn advance(&mut self, seconds: u64) {
+                self.app.update_block(|block| block.time = block.time.plus_seconds(seconds));
+            }
+
+            /// Queues rewards and lets the whole period stream out
+            fn fund(&mut self, amount: u128) {
+                self.queue(ADMIN, amount).unwrap();
+                self.advance(WEEK);
+            }
+
+            fn claim(&mut self, sender: &str, account: &str, forward_to: &str) {
+                let msg = ExecuteMsg::GetReward {
+                    account: account.to_string(),
+                    forward_to: forward_to.to_string(),
+                };
+                self.app
+                    .execute(Addr::unchecked(sender), self.pool.call(msg).unwrap())
+                    .unwrap();
+            }
+
+            fn earned(&self, account: &str) -> u128 {
+                let res: EarnedResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(
+                        self.pool.addr(),
+                        &QueryMsg::Earned {
+                            account: account.to_string(),
+                        },
+                    )
+                    .unwrap();
+                res.rewards[0].amount
+            }
+
+            fn staked(&self, account: &str) -> u128 {
+                let res: TokenAmountResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(
+                        self.pool.addr(),
+                        &QueryMsg::StakedBalance {
+                            account: account.to_string(),
+                        },
+                    )
+                    .unwrap();
+                res.amount
+            }
+
+            fn total_supply(&self) -> u128 {
+                let res: TokenAmountResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(self.pool.addr(), &QueryMsg::TotalSupply {})
+                    .unwrap();
+                res.amount
+            }
+
+            fn balance(&self, token: &Addr, account: &str) -> u128 {
+                let res: BalanceResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(
+                        token,
+                        &Cw20QueryMsg::Balance {
+                            address: account.to_string(),
+                        },
+                    )
+                    .unwrap();
+                res.balance.u128()
+            }
+        }
+
+        #[test]
+        fn deposit_forwards_lp_to_booster() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 300);
+            suite.deposit(ALICE, 50);
+
+            assert_eq!(suite.staked(ALICE), 150);
+            assert_eq!(suite.staked(BOB), 300);
+            assert_eq!(suite.total_supply(), 450);
+            assert_eq!(suite.balance(&suite.lp, suite.booster.as_str()), 450);
+            assert_eq!(suite.balance(&suite.lp, suite.pool.addr().as_str()), 0);
+        }
+
+        #[test]
+        fn deposit_rejects_other_tokens() {
+            let mut suite = setup();
+            let other = instantiate_cw20(&mut suite.app, "OTHER", &[ALICE]);
+
+            let msg = Cw20ExecuteMsg::Send {
+                contract: suite.pool.addr().to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&ReceiveMsg::Deposit {}).unwrap(),
+            };
+            suite
+                .app
+                .execute_contract(Addr::unchecked(ALICE), other, &msg, &[])
+                .unwrap_err();
+            assert_eq!(suite.total_supply(), 0);
+        }
+
+        #[test]
+        fn withdraw_returns_lp_from_booster() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.withdraw(ALICE, 40, false);
+
+            assert_eq!(suite.staked(ALICE), 60);
+            assert_eq!(suite.total_supply(), 60);
+            assert_eq!(suite.balance(&suite.lp, ALICE), 1_000_000 - 60);
+            assert_eq!(suite.balance(&suite.lp, suite.booster.as_str()), 60);
+
+            // cannot take out more than what is staked
+            let msg = ExecuteMsg::Withdraw { amount: 61, claim: false };
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.pool.call(msg).unwrap())
+                .unwrap_err();
+        }
+
+        #[test]
+        fn late_depositors_only_share_later_rewards() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.fund(1_000);
+
+            // bob joins after the first batch, with three times alice's stake
+            suite.deposit(BOB, 300);
+            suite.fund(400);
+
+            suite.deposit(CAROL, 100);
+            suite.fund(500);
+
+            assert_eq!(suite.earned(ALICE), 1_000 + 100 + 100);
+            assert_eq!(suite.earned(BOB), 300 + 300);
+            assert_eq!(suite.earned(CAROL), 100);
+
+            suite.claim(ALICE, ALICE, ALICE);
+            suite.claim(BOB, BOB, BOB);
+            suite.claim(CAROL, CAROL, CAROL);
+
+            let astro = suite.astro.clone();
+            assert_eq!(suite.balance(&astro, ALICE), 1_200);
+            assert_eq!(suite.balance(&astro, BOB), 600);
+            assert_eq!(suite.balance(&astro, CAROL), 100);
+            assert_eq!(suite.balance(&astro, suite.pool.addr().as_str()), 0);
+            assert_eq!(suite.earned(ALICE), 0);
+        }
+
+        #[test]
+        fn exited_depositor_keeps_accrued_rewards() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 100);
+            suite.fund(200);
+
+            suite.withdraw(ALICE, 100, false);
+            suite.fund(300);
+
+            assert_eq!(suite.earned(ALICE), 100);
+            assert_eq!(suite.earned(BOB), 400);
+
+            // claiming twice pays out only once
+            suite.claim(ALICE, ALICE, ALICE);
+            suite.claim(ALICE, ALICE, ALICE);
+            let astro = suite.astro.clone();
+            assert_eq!(suite.balance(&astro, ALICE), 100);
+
+            // new rewards after a claim are still tracked correctly
+            suite.fund(50);
+            suite.claim(BOB, BOB, BOB);
+            assert_eq!(suite.balance(&astro, BOB), 450);
+        }
+
+        #[test]
+        fn withdraw_can_claim_in_the_same_call() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 100);
+            suite.fund(500);
+
+            suite.withdraw(ALICE, 100, true);
+
+            let astro = suite.astro.clone();
+            assert_eq!(suite.balance(&astro, ALICE), 250);
+            assert_eq!(suite.earned(ALICE), 0);
+            assert_eq!(suite.earned(BOB), 250);
+        }
+
+        #[test]
+        fn only_account_can_forward_rewards() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.fund(1_000);
+
+            let msg = ExecuteMsg::GetReward {
+                account: ALICE.to_string(),
+                forward_to: BOB.to_string(),
+            };
+            suite
+                .app
+                .execute(Addr::unchecked(BOB), suite.pool.call(msg).unwrap())
+                .unwrap_err();
+
+            // anyone can still trigger a claim that pays alice's own address
+            suite.claim(BOB, ALICE, ALICE);
+            let astro = suite.astro.clone();
+            assert_eq!(suite.balance(&astro, ALICE), 1_000);
+
+            suite.fund(500);
+            suite.claim(ALICE, ALICE, CAROL);
+            assert_eq!(suite.balance(&astro, CAROL), 500);
+        }
+
+        #[test]
+        fn shutdown_freezes_rewards_and_only_allows_withdraw() {
+            let mut suite = setup();
+
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 300);
+            suite.fund(1_000);
+
+            // only the admin retires the pool
+            suite
+                .app
+                .execute(Addr::unchecked(ALICE), suite.pool.call(ExecuteMsg::Shutdown {}).unwrap())
+                .unwrap_err();
+            suite
+                .app
+                .execute(Addr::unchecked(ADMIN), suite.pool.call(ExecuteMsg::Shutdown {}).unwrap())
+                .unwrap();
+
+            // nothing can be queued after the shutdown checkpoint
+            assert_eq!(suite.queue(ADMIN, 4_000).unwrap_err(), "Pool 0 is shut down");
+            assert_eq!(suite.earned(ALICE), 250);
+            assert_eq!(suite.earned(BOB), 750);
+
+            let msg = Cw20ExecuteMsg::Send {
+                contract: suite.pool.addr().to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&ReceiveMsg::Deposit {}).unwrap(),
+            };
+            let err = suite
+                .app
+                .execute_contract(Addr::unchecked(CAROL), suite.lp.clone(), &msg, &[])
+                .unwrap_err();
+            assert_eq!(err.root_cause().to_string(), "Pool 0 is shut down");
+
+            suite.withdraw(ALICE, 100, true);
+            suite.claim(BOB, BOB, BOB);
+            let astro = suite.astro.clone();
+            assert_eq!(suite.balance(&astro, ALICE), 250);
+            assert_eq!(suite.balance(&astro, BOB), 750);
+            assert_eq!(suite.balance(&suite.lp, ALICE), 1_000_000);
+            assert_eq!(suite.earned(BOB), 0);
+        }
+
+        #[test]
+        fn shutdown_of_an_empty_pool_returns_queued_rewards() {
+            let mut suite = setup();
+            suite.deposit(ALICE, 100);
+            suite.queue(ADMIN, 7_000).unwrap();
+            suite.advance(86400);
+            suite.withdraw(ALICE, 100, false);
+
+            // the booster's last harvest lands while nobody is staked
+            let astro = suite.astro.clone();
+            let booster = suite.booster.to_string();
+            let msg = Cw20ExecuteMsg::Transfer { recipient: booster.clone(), amount: Uint128::new(500) };
+            suite.app.execute_contract(Addr::unchecked(ADMIN), astro.clone(), &msg, &[]).unwrap();
+            suite.queue(&booster, 500).unwrap();
+
+            suite.execute(ADMIN, ExecuteMsg::Shutdown {}, &[]).unwrap();
+            assert_eq!(suite.balance(&astro, MANAGER), 6_000 + 500);
+            assert_eq!(suite.balance(&astro, suite.pool.addr().as_str()), 1_000);
+
+            // alice keeps what streamed to her before she left
+            assert_eq!(suite.earned(ALICE), 1_000);
+            suite.claim(ALICE, ALICE, ALICE);
+            assert_eq!(suite.balance(&astro, ALICE), 1_000);
+        }
 
         #[test]
-        fn count() {
-            let (mut app, cw_template_contract) = proper_instantiate();
+        fn queued_rewards_stream_over_the_period() {
+            let mut suite = setup();
+            suite.deposit(ALICE, 100);
+
+            suite.queue(ADMIN, 7_000).unwrap();
+            assert_eq!(suite.earned(ALICE), 0);
+
+            suite.advance(86400);
+            assert_eq!(suite.earned(ALICE), 1_000);
+
+            // a late depositor only shares what streams after they join
+            suite.deposit(BOB, 100);
+            suite.advance(2 * 86400);
+            assert_eq!(suite.earned(ALICE), 2_000);
+            assert_eq!(suite.earned(BOB), 1_000);
+
+            let res: RewardPerTokenResponse = suite
+                .app
+                .wrap()
+                .query_wasm_smart(suite.pool.addr(), &QueryMsg::RewardPerToken {})
+                .unwrap();
+            assert_eq!(res.rewards[0].reward_per_token, 20 * 100_000_000_000_000_000_000);
+
+            // queueing again restarts the period with the leftover 4_000 plus the new 3_000
+            suite.queue(ADMIN, 3_000).unwrap();
+            suite.advance(WEEK / 2);
+            assert_eq!(suite.earned(ALICE), 2_000 + 1_750);
+            assert_eq!(suite.earned(BOB), 1_000 + 1_750);
+
+            suite.advance(WEEK);
+            assert_eq!(suite.earned(ALICE), 5_500);
+            assert_eq!(suite.earned(BOB), 4_500);
+        }
+
+        #[test]
+        fn rewards_queued_without_stakers_wait_for_the_first_depositor() {
+            let mut suite = setup();
+
+            // nobody is staked for the whole period
+            suite.queue(ADMIN, 7_000).unwrap();
+            suite.advance(WEEK + 86400);
+
+            // the period restarts when the first depositor checkpoints
+            suite.deposit(ALICE, 100);
+            suite.claim(ALICE, ALICE, ALICE);
+            assert_eq!(suite.balance(&suite.astro.clone(), ALICE), 0);
+            assert_eq!(suite.earned(ALICE), 0);
+
+            suite.advance(86400);
+            suite.deposit(BOB, 100);
+            assert_eq!(suite.earned(ALICE), 1_000);
+
+            suite.advance(WEEK);
+            assert_eq!(suite.earned(ALICE), 4_000);
+            assert_eq!(suite.earned(BOB), 3_000);
+        }
+
+        #[test]
+        fn stream_pauses_while_the_pool_is_empty() {
+            let mut suite = setup();
+            suite.deposit(ALICE, 100);
+            suite.queue(ADMIN, 7_000).unwrap();
+
+            suite.advance(2 * 86400);
+            suite.withdraw(ALICE, 100, true);
+            assert_eq!(suite.balance(&suite.astro.clone(), ALICE), 2_000);
+
+            // the five days left resume once someone stakes again
+            suite.advance(WEEK);
+            suite.deposit(BOB, 100);
+            suite.advance(86400);
+            assert_eq!(suite.earned(BOB), 1_000);
+            suite.advance(WEEK);
+            assert_eq!(suite.earned(BOB), 5_000);
+        }
+
+        #[test]
+        fn reward_manager_sets_the_duration() {
+            let mut suite = setup();
+            suite.deposit(ALICE, 100);
+
+            let msg = ExecuteMsg::SetRewardDuration { duration: WEEK / 7 };
+            assert_eq!(suite.execute(ALICE, msg.clone(), &[]).unwrap_err(), "Unauthorized");
+            assert_eq!(
+                suite.execute(MANAGER, ExecuteMsg::SetRewardDuration { duration: 0 }, &[]).unwrap_err(),
+                "Reward duration must be greater than zero"
+            );
+            suite.execute(MANAGER, msg, &[]).unwrap();
+
+            suite.queue(ADMIN, 1_000).unwrap();
+            suite.advance(86400);
+            assert_eq!(suite.earned(ALICE), 1_000);
+
+            // the role can be handed over, after which the old manager is locked out
+            let msg = ExecuteMsg::SetRewardManager { reward_manager: BOB.to_string() };
+            suite.execute(MANAGER, msg, &[]).unwrap();
+            let msg = ExecuteMsg::SetRewardDuration { duration: WEEK };
+            assert_eq!(suite.execute(MANAGER, msg.clone(), &[]).unwrap_err(), "Unauthorized");
+            suite.execute(BOB, msg, &[]).unwrap();
+        }
+
+        #[test]
+        fn only_booster_or_admin_queue_reward_tokens() {
+            let mut suite = setup();
+            suite.deposit(ALICE, 100);
+
+            let (astro, booster) = (suite.astro.clone(), suite.booster.clone());
+            let msg = Cw20ExecuteMsg::Transfer { recipient: BOB.to_string(), amount: Uint128::new(100) };
+            suite.app.execute_contract(Addr::unchecked(ADMIN), astro.clone(), &msg, &[]).unwrap();
+            assert_eq!(suite.queue(BOB, 100).unwrap_err(), "Unauthorized");
+
+            let msg = Cw20ExecuteMsg::Transfer { recipient: booster.to_string(), amount: Uint128::new(100) };
+            suite.app.execute_contract(Addr::unchecked(ADMIN), astro, &msg, &[]).unwrap();
+            suite.queue(booster.as_str(), 100).unwrap();
+
+            let other = instantiate_cw20(&mut suite.app, "OTHER", &[ADMIN]);
+            let msg = Cw20ExecuteMsg::Send {
+                contract: suite.pool.addr().to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&ReceiveMsg::QueueNewRewards {}).unwrap(),
+            };
+            let err = suite.app.execute_contract(Addr::unchecked(ADMIN), other.clone(), &msg, &[]).unwrap_err();
+            assert_eq!(err.root_cause().to_string(), format!("{} is not a reward token", other));
+        }
+
+        #[test]
+        fn admin_manages_reward_tokens() {
+            let mut suite = setup();
+            let native = Denom::Native(NATIVE_DENOM.to_string());
+            let add = |token: &Denom| ExecuteMsg::AddRewardToken { token: token.clone() };
+
+            assert_eq!(suite.execute(ALICE, add(&native), &[]).unwrap_err(), "Unauthorized");
+            suite.execute(ADMIN, add(&native), &[]).unwrap();
+            assert_eq!(
+                suite.execute(ADMIN, add(&native), &[]).unwrap_err(),
+                format!("{} is already a reward token", NATIVE_DENOM)
+            );
+            let astro = Denom::Cw20(suite.astro.clone());
+            assert_eq!(
+                suite.execute(ADMIN, add(&astro), &[]).unwrap_err(),
+                format!("{} is already a reward token", suite.astro)
+            );
+
+            // the reward manager shares the admin's say over reward tokens
+            for i in 0..6 {
+                suite.execute(MANAGER, add(&Denom::Native(format!("extra{}", i))), &[]).unwrap();
+            }
+            assert_eq!(
+                suite.execute(ADMIN, add(&Denom::Native("ninth".to_string())), &[]).unwrap_err(),
+                "Cannot add more than 8 reward tokens"
+            );
+
+            let msg = ExecuteMsg::RemoveRewardToken { token: native.clone() };
+            assert_eq!(suite.execute(ALICE, msg.clone(), &[]).unwrap_err(), "Unauthorized");
+            suite.execute(ADMIN, msg.clone(), &[]).unwrap();
+            assert_eq!(suite.execute(ADMIN, msg, &[]).unwrap_err(), format!("{} is not a reward token", NATIVE_DENOM));
+
+            // the booster's harvests always arrive as ASTRO
+            let msg = ExecuteMsg::RemoveRewardToken { token: astro.clone() };
+            assert_eq!(
+                suite.execute(MANAGER, msg, &[]).unwrap_err(),
+                format!("{} is harvested by the booster and cannot be removed", suite.astro)
+            );
+
+            let tokens = suite.reward_tokens();
+            assert_eq!(tokens.len(), 8);
+            assert_eq!(tokens[0], (astro, true));
+            assert_eq!(tokens[1], (native.clone(), false));
+
+            // a removed token can no longer be queued until it is added back
+            let queue = ExecuteMsg::QueueNewRewards {};
+            assert_eq!(
+                suite.execute(ADMIN, queue.clone(), &coins(100, NATIVE_DENOM)).unwrap_err(),
+                format!("{} is not a reward token", NATIVE_DENOM)
+            );
+            suite.execute(ADMIN, add(&native), &[]).unwrap();
+            suite.execute(ADMIN, queue, &coins(100, NATIVE_DENOM)).unwrap();
+            assert_eq!(suite.reward_tokens()[1], (native, true));
+        }
+
+        #[test]
+        fn native_rewards_stream_and_pay_out() {
+            let mut suite = setup();
+            let native = Denom::Native(NATIVE_DENOM.to_string());
+            suite.execute(ADMIN, ExecuteMsg::AddRewardToken { token: native.clone() }, &[]).unwrap();
+            suite.deposit(ALICE, 100);
+            suite.deposit(BOB, 300);
+
+            suite.execute(ADMIN, ExecuteMsg::QueueNewRewards {}, &coins(8_000, NATIVE_DENOM)).unwrap();
+            suite.advance(WEEK / 2);
+
+            // removing the token midway still streams what was already queued
+            suite.execute(ADMIN, ExecuteMsg::RemoveRewardToken { token: native }, &[]).unwrap();
+            suite.advance(WEEK);
 
-            // let msg = ExecuteMsg::Stake{};
-            // let cosmos_msg = cw_template_contract.call(msg).unwrap();
-            // app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            suite.claim(ALICE, ALICE, ALICE);
+            suite.claim(BOB, BOB, CAROL);
+            let bank = |app: &App, account: &str| app.wrap().query_balance(account, NATIVE_DENOM).unwrap().amount.u128();
+            assert_eq!(bank(&suite.app, ALICE), 2_000);
+            assert_eq!(bank(&suite.app, CAROL), 6_000);
+            assert_eq!(bank(&suite.app, BOB), 0);
         }
     }
 }
@@ -1,6 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use cosmwasm_std::Uint128;
+use cw20::{Cw20ReceiveMsg, Denom};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: String,
@@ -9,16 +12,60 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Init{astro_token: String, astro_guage: String, octane_staker: String, octane_booster: String, lp_token: String, pool_id: u128},
-    GetReward{account: String, forward_to: String}
+    Init{astro_token: String, astro_guage: String, octane_staker: String, octane_booster: String, lp_token: String, oastro_token: String, pool_id: u128, reward_manager: String},
+    GetReward{account: String, forward_to: String},
+    // Receive takes LP tokens sent with a ReceiveMsg hook
+    Receive(Cw20ReceiveMsg),
+    Withdraw{amount: u128, claim: bool},
+    // Shutdown freezes rewards at the current checkpoint, only withdrawals are accepted afterwards.
+    // Rewards still queued in an empty pool are sent back to the reward manager
+    Shutdown{},
+    // SetRewardDuration changes how long the next queue streams for, admin or reward manager only
+    SetRewardDuration{duration: u64},
+    // SetRewardManager hands the reward manager role over, admin or reward manager only
+    SetRewardManager{reward_manager: String},
+    // AddRewardToken accepts a native denom or cw20 address as an extra reward, admin or reward manager only
+    AddRewardToken{token: Denom},
+    // RemoveRewardToken stops new queues for the token, what is already queued still streams and stays claimable.
+    // ASTRO can't be removed since the booster queues every harvest in it
+    RemoveRewardToken{token: Denom},
+    // QueueNewRewards streams the attached native reward tokens, booster, admin or reward manager only
+    QueueNewRewards{},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Deposit{},
+    // QueueNewRewards streams the sent reward tokens over the next reward_duration, booster, admin or reward manager only
+    QueueNewRewards{},
+}
+
+/// Hook sent along with the LP when it is forwarded to the octane booster
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoosterReceiveMsg {
+    Deposit{pool_id: u128},
+}
 
+/// Booster messages the reward pool calls to release staked LP
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoosterExecuteMsg {
+    Withdraw{pool_id: u128, amount: Uint128, recipient: String},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    // GetCount returns the current count as a json-encoded number
-    GetTokenAmount {addr: String},
+    // Earned returns what the account could claim right now for every reward token
+    Earned {account: String},
+    StakedBalance {account: String},
+    TotalSupply {},
+    // RewardPerToken returns the current reward_integral of every reward token, scaled by 1e20
+    RewardPerToken {},
+    // RewardTokens lists every reward token, including deactivated ones
+    RewardTokens {},
 }
 
 // We define a custom struct for each query response
@@ -26,3 +73,37 @@ pub enum QueryMsg {
 pub struct TokenAmountResponse {
     pub amount: u128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarnedData {
+    pub token: Denom,
+    pub amount: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarnedResponse {
+    pub rewards: Vec<EarnedData>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardPerTokenData {
+    pub token: Denom,
+    pub reward_per_token: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardPerTokenResponse {
+    pub rewards: Vec<RewardPerTokenData>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardTokenData {
+    pub token: Denom,
+    pub active: bool,
+    pub period_finish: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardTokensResponse {
+    pub tokens: Vec<RewardTokenData>,
+}
@@ -2,13 +2,19 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
+use cw20::Denom;
 use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct RewardType{
-    pub reward_token: Addr,
+    pub reward_token: Denom,
+    /// Inactive tokens can no longer be queued but keep streaming and paying out what is left
+    pub active: bool,
     pub reward_integral: u128,
-    pub reward_remaining: u128
+    /// Queued rewards that have not been streamed into reward_integral yet
+    pub reward_remaining: u128,
+    pub period_finish: u64,
+    pub last_update_time: u64
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -24,9 +30,31 @@ pub struct Config{
     pub octane_staker: Addr,
     pub octane_booster: Addr,
     pub lp_token: Addr,
-    pub pool_id: u128
+    pub oastro_token: Addr,
+    pub pool_id: u128,
+    pub shutdown: bool,
+    /// Seconds each batch of queued rewards is streamed over
+    pub reward_duration: u64,
+    /// Manages the reward schedule next to the admin, which is the astro_stake contract in deployment
+    pub reward_manager: Addr
 }
 
-pub const CONFIG: Item<Config> = Item::new("config"); 
+pub const CONFIG: Item<Config> = Item::new("config");
 
-pub const REWARD_MAP: Map<Addr, u128> = Map::new("reward_map");
\ No newline at end of file
+/// Claimable amount not yet paid out, keyed by (denom_key, account)
+pub const REWARD_MAP: Map<(String, Addr), u128> = Map::new("reward_map");
+
+/// The reward_integral an account was last checkpointed at, keyed by (denom_key, account)
+pub const REWARD_INTEGRAL_FOR: Map<(String, Addr), u128> = Map::new("reward_integral_for");
+
+/// Staked balance of every depositor
+pub const BALANCES: Map<Addr, u128> = Map::new("lp_balances");
+
+pub const TOTAL_SUPPLY: Item<u128> = Item::new("lp_total_supply");
+
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(token) => format!("cw20:{}", token),
+    }
+}
@@ -0,0 +1,256 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128, Uint64, WasmMsg};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, LockData, LocksResponse, QueryMsg, ReceiveMsg, TokenAmountResponse};
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::state::{CONFIG, Config, PROCESSED_UNTIL, TOTAL_LOCKS, USER_LOCKS, epoch_at};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:octane_vote_lock";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    if msg.lock_epochs == 0 {
+        return Err(ContractError::ZeroLockEpochs {  });
+    }
+
+    let config = Config{
+        lock_token: deps.api.addr_validate(&msg.lock_token)?,
+        lock_epochs: msg.lock_epochs,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("lock_token", msg.lock_token)
+        .add_attribute("lock_epochs", msg.lock_epochs.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::ProcessExpiredLocks { relock } => process_expired_locks(deps, env, info, relock),
+    }
+}
+
+fn receive(deps: DepsMut, env: Env, info: MessageInfo, wrapper: Cw20ReceiveMsg) -> Result<Response, ContractError>{
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match msg {
+        ReceiveMsg::Lock{} => lock(deps, env, info, sender, wrapper.amount.u128()),
+    }
+}
+
+fn lock(deps: DepsMut, env: Env, info: MessageInfo, account: Addr, amount: u128) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    // info.sender is the cw20 contract that forwarded the hook
+    if info.sender != config.lock_token {
+        return Err(ContractError::InvalidLockToken { expected: config.lock_token.into() });
+    }
+    if amount == 0 {
+        return Err(ContractError::ZeroAmount {  });
+    }
+
+    let unlock_epoch = add_lock(deps.storage, &config, &env, &account, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "lock")
+        .add_attribute("account", account)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("unlock_epoch", unlock_epoch.to_string()))
+}
+
+/// A lock only counts from the next epoch, so weight cannot be added to an epoch that is
+/// already being voted on.
+fn add_lock(storage: &mut dyn Storage, config: &Config, env: &Env, account: &Addr, amount: u128) -> StdResult<u64> {
+    let unlock_epoch = Uint64::new(epoch_at(env.block.time.seconds()))
+        .checked_add(Uint64::new(1))?
+        .checked_add(Uint64::new(config.lock_epochs))?
+        .u64();
+
+    USER_LOCKS.update(storage, (account.clone(), unlock_epoch), |locked| -> StdResult<_> {
+        Ok(locked.unwrap_or_default() + amount)
+    })?;
+    TOTAL_LOCKS.update(storage, unlock_epoch, |locked| -> StdResult<_> {
+        Ok(locked.unwrap_or_default() + amount)
+    })?;
+
+    Ok(unlock_epoch)
+}
+
+fn process_expired_locks(deps: DepsMut, env: Env, info: MessageInfo, relock: bool) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = epoch_at(env.block.time.seconds());
+    let processed_until = PROCESSED_UNTIL.may_load(deps.storage, info.sender.clone())?;
+
+    let expired = sum_locks(deps.storage, &info.sender, processed_until, current_epoch)?;
+    if expired == 0 {
+        return Err(ContractError::NoExpiredLocks {  });
+    }
+    PROCESSED_UNTIL.save(deps.storage, info.sender.clone(), &current_epoch)?;
+
+    let res = Response::new()
+        .add_attribute("method", "process_expired_locks")
+        .add_attribute("account", info.sender.clone())
+        .add_attribute("amount", expired.to_string());
+
+    if relock {
+        let unlock_epoch = add_lock(deps.storage, &config, &env, &info.sender, expired)?;
+        return Ok(res.add_attribute("unlock_epoch", unlock_epoch.to_string()));
+    }
+
+    let withdraw_msg = WasmMsg::Execute {
+        contract_addr: config.lock_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: info.sender.to_string(), amount: Uint128::new(expired) })?,
+        funds: vec![],
+    };
+
+    Ok(res.add_message(withdraw_msg))
+}
+
+/// Sum of the account's locks unlocking after `after` and at or before `until`
+fn sum_locks(storage: &dyn Storage, account: &Addr, after: Option<u64>, until: u64) -> StdResult<u128> {
+    USER_LOCKS
+        .prefix(account.clone())
+        .range(storage, after.map(Bound::exclusive), Some(Bound::inclusive(until)), Order::Ascending)
+        .map(|item| item.map(|(_, amount)| amount))
+        .sum()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::LockedBalanceOf { addr, epoch } => to_binary(&query_locked_balance_of(deps, addr, epoch)?),
+        QueryMsg::TotalLockedAt { epoch } => to_binary(&query_total_locked_at(deps, epoch)?),
+        QueryMsg::Locks { addr } => to_binary(&query_locks(deps, env, addr)?),
+    }
+}
+
+/// A lock counts during every epoch from unlock_epoch - lock_epochs up to unlock_epoch,
+/// exclusive, so only locks unlocking within lock_epochs after `epoch` are summed.
+fn query_locked_balance_of(deps: Deps, addr: String, epoch: u64) -> StdResult<TokenAmountResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let amount = sum_locks(deps.storage, &addr, Some(epoch), epoch.saturating_add(config.lock_epochs))?;
+    Ok(TokenAmountResponse { amount })
+}
+
+fn query_total_locked_at(deps: Deps, epoch: u64) -> StdResult<TokenAmountResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let amount = TOTAL_LOCKS
+        .range(deps.storage, Some(Bound::exclusive(epoch)), Some(Bound::inclusive(epoch.saturating_add(config.lock_epochs))), Order::Ascending)
+        .map(|item| item.map(|(_, amount)| amount))
+        .sum::<StdResult<u128>>()?;
+    Ok(TokenAmountResponse { amount })
+}
+
+fn query_locks(deps: Deps, env: Env, addr: String) -> StdResult<LocksResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let current_epoch = epoch_at(env.block.time.seconds());
+    let processed_until = PROCESSED_UNTIL.may_load(deps.storage, addr.clone())?;
+
+    let locks = USER_LOCKS
+        .prefix(addr)
+        .range(deps.storage, processed_until.map(Bound::exclusive), None, Order::Ascending)
+        .map(|item| item.map(|(unlock_epoch, amount)| LockData { amount, unlock_epoch }))
+        .collect::<StdResult<Vec<_>>>()?;
+    let unlockable = locks
+        .iter()
+        .filter(|lock| lock.unlock_epoch <= current_epoch)
+        .map(|lock| lock.amount)
+        .sum();
+
+    Ok(LocksResponse { locks, unlockable })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::StdError;
+
+    const LOCK_TOKEN: &str = "oastro";
+
+    fn get_instantiate_msg(lock_epochs: u64) -> InstantiateMsg{
+        InstantiateMsg { lock_token: LOCK_TOKEN.to_string(), lock_epochs }
+    }
+
+    fn lock_msg(sender: &str, amount: u128) -> ExecuteMsg {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&ReceiveMsg::Lock {}).unwrap(),
+        })
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg(0)).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroLockEpochs {}));
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg(16)).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&res).unwrap();
+        assert_eq!(config.lock_token, Addr::unchecked(LOCK_TOKEN));
+        assert_eq!(config.lock_epochs, 16);
+    }
+
+    #[test]
+    fn lock_only_accepts_the_lock_token() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg(16)).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("other", &[]), lock_msg("alice", 100)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidLockToken { .. }));
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(LOCK_TOKEN, &[]), lock_msg("alice", 0)).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroAmount {}));
+
+        execute(deps.as_mut(), mock_env(), mock_info(LOCK_TOKEN, &[]), lock_msg("alice", 100)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::ProcessExpiredLocks { relock: false }).unwrap_err();
+        assert!(matches!(err, ContractError::NoExpiredLocks {}));
+    }
+
+    #[test]
+    fn epoch_arithmetic_does_not_overflow() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg(u64::MAX)).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(LOCK_TOKEN, &[]), lock_msg("alice", 100)).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+        let msg = QueryMsg::LockedBalanceOf { addr: "alice".to_string(), epoch: u64::MAX - 1 };
+        let res: TokenAmountResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.amount, 0);
+        let msg = QueryMsg::TotalLockedAt { epoch: u64::MAX - 1 };
+        let res: TokenAmountResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.amount, 0);
+    }
+}
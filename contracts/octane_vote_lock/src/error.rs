@@ -0,0 +1,20 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Invalid lock token, expected {expected}")]
+    InvalidLockToken { expected: String },
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Locks must last at least one epoch")]
+    ZeroLockEpochs {},
+
+    #[error("No expired locks to process")]
+    NoExpiredLocks {},
+}
@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use crate::helpers::CwTemplateContract;
+    use crate::msg::{ExecuteMsg, InstantiateMsg, LockData, LocksResponse, QueryMsg, ReceiveMsg, TokenAmountResponse};
+    use crate::state::{epoch_at, EPOCH_LENGTH};
+    use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+    pub fn contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const ADMIN: &str = "admin";
+    const ALICE: &str = "alice";
+    const BOB: &str = "bob";
+    const LOCK_EPOCHS: u64 = 4;
+
+    struct Suite {
+        app: App,
+        vote_lock: CwTemplateContract,
+        token: Addr,
+    }
+
+    fn setup() -> Suite {
+        let mut app = App::default();
+
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: "oAstro".to_string(),
+            symbol: "OASTRO".to_string(),
+            decimals: 6,
+            initial_balances: [ALICE, BOB]
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(1_000),
+                })
+                .collect(),
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        };
+        let token = app
+            .instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], "oastro", None)
+            .unwrap();
+
+        let vote_lock_id = app.store_code(contract_template());
+        let msg = InstantiateMsg { lock_token: token.to_string(), lock_epochs: LOCK_EPOCHS };
+        let vote_lock = app
+            .instantiate_contract(vote_lock_id, Addr::unchecked(ADMIN), &msg, &[], "vote_lock", None)
+            .unwrap();
+
+        Suite { app, vote_lock: CwTemplateContract(vote_lock), token }
+    }
+
+    impl Suite {
+        fn epoch(&self) -> u64 {
+            epoch_at(self.app.block_info().time.seconds())
+        }
+
+        fn advance_epochs(&mut self, epochs: u64) {
+            self.app.update_block(|block| block.time = block.time.plus_seconds(epochs * EPOCH_LENGTH));
+        }
+
+        fn lock(&mut self, account: &str, amount: u128) {
+            let msg = Cw20ExecuteMsg::Send {
+                contract: self.vote_lock.addr().to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&ReceiveMsg::Lock {}).unwrap(),
+            };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.token.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        fn process(&mut self, account: &str, relock: bool) -> Result<(), String> {
+            self.app
+                .execute(Addr::unchecked(account), self.vote_lock.call(ExecuteMsg::ProcessExpiredLocks { relock }).unwrap())
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn locked(&self, account: &str, epoch: u64) -> u128 {
+            self.vote_lock
+                .locked_balance_of::<_, _, Empty>(&self.app, account, epoch)
+                .unwrap()
+                .amount
+        }
+
+        fn total(&self, epoch: u64) -> u128 {
+            let res: TokenAmountResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(self.vote_lock.addr(), &QueryMsg::TotalLockedAt { epoch })
+                .unwrap();
+            res.amount
+        }
+
+        fn locks(&self, account: &str) -> LocksResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(self.vote_lock.addr(), &QueryMsg::Locks { addr: account.to_string() })
+                .unwrap()
+        }
+
+        fn balance(&self, account: &str) -> u128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(&self.token, &Cw20QueryMsg::Balance { address: account.to_string() })
+                .unwrap();
+            res.balance.u128()
+        }
+    }
+
+    #[test]
+    fn locks_count_from_the_next_epoch_for_lock_epochs() {
+        let mut suite = setup();
+        let start = suite.epoch();
+
+        suite.lock(ALICE, 100);
+        suite.lock(BOB, 50);
+        suite.advance_epochs(1);
+        suite.lock(ALICE, 30);
+
+        assert_eq!(suite.locked(ALICE, start), 0);
+        assert_eq!(suite.locked(ALICE, start + 1), 100);
+        assert_eq!(suite.locked(ALICE, start + 2), 130);
+        assert_eq!(suite.locked(ALICE, start + 4), 130);
+        assert_eq!(suite.locked(ALICE, start + 5), 30);
+        assert_eq!(suite.locked(ALICE, start + 6), 0);
+
+        assert_eq!(suite.total(start), 0);
+        assert_eq!(suite.total(start + 1), 150);
+        assert_eq!(suite.total(start + 2), 180);
+        assert_eq!(suite.total(start + 5), 30);
+
+        let locks = suite.locks(ALICE);
+        assert_eq!(locks.locks, vec![
+            LockData { amount: 100, unlock_epoch: start + 1 + LOCK_EPOCHS },
+            LockData { amount: 30, unlock_epoch: start + 2 + LOCK_EPOCHS },
+        ]);
+        assert_eq!(locks.unlockable, 0);
+    }
+
+    #[test]
+    fn expired_locks_can_be_withdrawn_or_relocked() {
+        let mut suite = setup();
+        let start = suite.epoch();
+        suite.lock(ALICE, 100);
+        suite.lock(BOB, 100);
+
+        suite.advance_epochs(LOCK_EPOCHS);
+        assert_eq!(suite.process(ALICE, false).unwrap_err(), "No expired locks to process");
+
+        suite.advance_epochs(1);
+        assert_eq!(suite.locks(ALICE).unlockable, 100);
+        suite.process(ALICE, false).unwrap();
+        assert_eq!(suite.balance(ALICE), 1_000);
+        assert_eq!(suite.process(ALICE, false).unwrap_err(), "No expired locks to process");
+        assert!(suite.locks(ALICE).locks.is_empty());
+        // past epochs keep their weight after withdrawal
+        assert_eq!(suite.locked(ALICE, start + 2), 100);
+
+        // relocked tokens count again from the next epoch, like a new lock
+        suite.process(BOB, true).unwrap();
+        assert_eq!(suite.balance(BOB), 900);
+        let now = suite.epoch();
+        assert_eq!(suite.locked(BOB, now), 0);
+        assert_eq!(suite.locked(BOB, now + 1), 100);
+        assert_eq!(suite.total(now + LOCK_EPOCHS), 100);
+        assert_eq!(suite.locks(BOB).locks, vec![LockData { amount: 100, unlock_epoch: now + 1 + LOCK_EPOCHS }]);
+    }
+}
@@ -0,0 +1,55 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw20::Cw20ReceiveMsg;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub lock_token: String,
+    pub lock_epochs: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Receive takes lock tokens sent with a ReceiveMsg hook
+    Receive(Cw20ReceiveMsg),
+    // ProcessExpiredLocks withdraws every expired lock of the sender, or locks it again for lock_epochs
+    ProcessExpiredLocks{relock: bool},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    // Lock counts from the next epoch for lock_epochs epochs
+    Lock{},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    // LockedBalanceOf returns the voting weight of addr during epoch
+    LockedBalanceOf {addr: String, epoch: u64},
+    TotalLockedAt {epoch: u64},
+    // Locks lists the locks of addr that have not been withdrawn or relocked yet
+    Locks {addr: String},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenAmountResponse {
+    pub amount: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockData {
+    pub amount: u128,
+    pub unlock_epoch: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LocksResponse {
+    pub locks: Vec<LockData>,
+    // sum of the locks that have already expired
+    pub unlockable: u128,
+}
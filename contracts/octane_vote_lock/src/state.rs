@@ -0,0 +1,31 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+/// Locks are counted in weekly epochs starting at the unix epoch
+pub const EPOCH_LENGTH: u64 = 7 * 86400;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config{
+    pub lock_token: Addr,
+    /// Number of epochs a lock counts for, starting from the epoch after it was made
+    pub lock_epochs: u64
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Amount each account has unlocking at an epoch, keyed by (account, unlock_epoch).
+/// Entries are kept after withdrawal so past balances can still be queried.
+pub const USER_LOCKS: Map<(Addr, u64), u128> = Map::new("user_locks");
+
+/// Amount unlocking at an epoch across all accounts
+pub const TOTAL_LOCKS: Map<u64, u128> = Map::new("total_locks");
+
+/// Locks unlocking at or before this epoch have been withdrawn or relocked
+pub const PROCESSED_UNTIL: Map<Addr, u64> = Map::new("processed_until");
+
+pub fn epoch_at(seconds: u64) -> u64 {
+    seconds / EPOCH_LENGTH
+}
@@ -0,0 +1,295 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128, WasmMsg};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::msg::{EpochVotesResponse, ExecuteMsg, GeneratorControllerExecuteMsg, InstantiateMsg, LastCastEpochResponse, PoolVoteData, QueryMsg, UserVotesResponse};
+
+use astro_stake::msg::{PoolResponse, QueryMsg as PoolRegistryQueryMsg};
+use cw_controllers::Admin;
+use octane_vote_lock::msg::{QueryMsg as VoteLockQueryMsg, TokenAmountResponse};
+use octane_vote_lock::state::epoch_at;
+
+use crate::state::{BPS_DENOMINATOR, CONFIG, Config, LAST_CAST_EPOCH, POOL_VOTES, USER_VOTES, UserVote, pool_weight};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:octane_voter";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const SUPER_ADMIN: &Admin = &Admin::new("SUPER_ADMIN");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let super_admin_addr = deps.api.addr_validate(&msg.admin)?;
+    SUPER_ADMIN.set(deps.branch(), Some(super_admin_addr))?;
+
+    let config = Config{
+        vote_lock: deps.api.addr_validate(&msg.vote_lock)?,
+        pool_registry: deps.api.addr_validate(&msg.pool_registry)?,
+        generator_controller: deps.api.addr_validate(&msg.generator_controller)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("owner", msg.admin))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Vote { votes } => vote(deps, env, info, votes),
+        ExecuteMsg::CastVotes {} => cast_votes(deps, env),
+        ExecuteMsg::SetGeneratorController { generator_controller } => set_generator_controller(deps, info, generator_controller),
+    }
+}
+
+/// Lock weight for an epoch is fixed once the epoch starts, since new locks only count
+/// from the next one, so votes can be re-weighted with the same power they were added with.
+fn vote(deps: DepsMut, env: Env, info: MessageInfo, votes: Vec<(String, u16)>) -> Result<Response, ContractError>{
+    let epoch = epoch_at(env.block.time.seconds());
+    let power = query_voting_power(deps.as_ref(), &info.sender, epoch)?;
+    if power == 0 {
+        return Err(ContractError::NoVotingPower { epoch });
+    }
+
+    let mut validated: Vec<(Addr, u16)> = vec![];
+    for (pool, bps) in votes {
+        let pool_addr = deps.api.addr_validate(&pool)?;
+        if validated.iter().any(|(voted, _)| *voted == pool_addr) {
+            return Err(ContractError::DuplicatePool { pool });
+        }
+        if bps > 0 {
+            // the generator controller rejects pools it doesn't know, which would fail the whole cast
+            check_active_pool(deps.as_ref(), &pool)?;
+            validated.push((pool_addr, bps));
+        }
+    }
+    let total_bps: u32 = validated.iter().map(|(_, bps)| *bps as u32).sum();
+    if total_bps > BPS_DENOMINATOR as u32 {
+        return Err(ContractError::VotesTooHigh {  });
+    }
+
+    let key = (epoch, info.sender.clone());
+    if let Some(previous) = USER_VOTES.may_load(deps.storage, key.clone())? {
+        for (pool, bps) in previous.votes {
+            let weight = pool_weight(previous.power, bps);
+            let remaining = POOL_VOTES.load(deps.storage, (epoch, pool.clone()))? - weight;
+            if remaining == 0 {
+                POOL_VOTES.remove(deps.storage, (epoch, pool));
+            } else {
+                POOL_VOTES.save(deps.storage, (epoch, pool), &remaining)?;
+            }
+        }
+    }
+    for (pool, bps) in validated.iter() {
+        POOL_VOTES.update(deps.storage, (epoch, pool.clone()), |weight| -> StdResult<_> {
+            Ok(weight.unwrap_or_default() + pool_weight(power, *bps))
+        })?;
+    }
+    USER_VOTES.save(deps.storage, key, &UserVote { power, votes: validated })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "vote")
+        .add_attribute("voter", info.sender)
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("power", power.to_string()))
+}
+
+fn cast_votes(deps: DepsMut, env: Env) -> Result<Response, ContractError>{
+    let config = CONFIG.load(deps.storage)?;
+    let current = epoch_at(env.block.time.seconds());
+    let epoch = match current.checked_sub(1) {
+        Some(epoch) => epoch,
+        // nothing has been voted on before the first epoch ends
+        None => return Err(ContractError::NoVotes { epoch: current }),
+    };
+    if matches!(LAST_CAST_EPOCH.may_load(deps.storage)?, Some(last) if last >= epoch) {
+        return Err(ContractError::VotesAlreadyCast { epoch });
+    }
+
+    let (_, pooled) = pooled_votes(deps.storage, epoch)?;
+    let votes: Vec<(String, u16)> = pooled
+        .into_iter()
+        .filter(|vote| vote.bps > 0)
+        .map(|vote| (vote.pool.to_string(), vote.bps))
+        .collect();
+    if votes.is_empty() {
+        return Err(ContractError::NoVotes { epoch });
+    }
+    LAST_CAST_EPOCH.save(deps.storage, &epoch)?;
+
+    let vote_msg = WasmMsg::Execute {
+        contract_addr: config.generator_controller.to_string(),
+        msg: to_binary(&GeneratorControllerExecuteMsg::Vote { votes })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(vote_msg)
+        .add_attribute("method", "cast_votes")
+        .add_attribute("epoch", epoch.to_string()))
+}
+
+fn set_generator_controller(mut deps: DepsMut, info: MessageInfo, generator_controller: String) -> Result<Response, ContractError>{
+    check_admin(deps.branch(), info)?;
+
+    let generator_controller_addr = deps.api.addr_validate(&generator_controller)?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.generator_controller = generator_controller_addr;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_generator_controller")
+        .add_attribute("generator_controller", generator_controller))
+}
+
+/// Every pool's weight for the epoch and its pro rata share in basis points, rounded down
+/// so the shares never add up to more than 10_000.
+fn pooled_votes(storage: &dyn Storage, epoch: u64) -> StdResult<(u128, Vec<PoolVoteData>)> {
+    let weights = POOL_VOTES
+        .prefix(epoch)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let total_weight: u128 = weights.iter().map(|(_, weight)| weight).sum();
+
+    let votes = weights
+        .into_iter()
+        .map(|(pool, weight)| PoolVoteData {
+            pool,
+            weight,
+            bps: Uint128::new(weight).multiply_ratio(BPS_DENOMINATOR, total_weight).u128() as u16,
+        })
+        .collect();
+
+    Ok((total_weight, votes))
+}
+
+fn check_active_pool(deps: Deps, pool: &str) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let res: PoolResponse = deps.querier
+        .query_wasm_smart(config.pool_registry, &PoolRegistryQueryMsg::PoolByLpToken { lp_token: pool.to_string() })
+        .map_err(|_| ContractError::InvalidPool { pool: pool.to_string() })?;
+    if !res.pool.active {
+        return Err(ContractError::InvalidPool { pool: pool.to_string() });
+    }
+    Ok(())
+}
+
+fn query_voting_power(deps: Deps, addr: &Addr, epoch: u64) -> StdResult<u128> {
+    let config = CONFIG.load(deps.storage)?;
+    let res: TokenAmountResponse = deps.querier.query_wasm_smart(
+        config.vote_lock,
+        &VoteLockQueryMsg::LockedBalanceOf { addr: addr.to_string(), epoch },
+    )?;
+    Ok(res.amount)
+}
+
+fn check_admin(deps: DepsMut, info: MessageInfo) -> Result<(), ContractError>{
+    let super_admin = SUPER_ADMIN.assert_admin(deps.as_ref(), &info.sender);
+
+    match super_admin {
+        Ok(()) => Ok(()),
+        Err(_not_admin) => Err(ContractError::Unauthorized {  })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::CurrentVotes {} => to_binary(&query_epoch_votes(deps, epoch_at(env.block.time.seconds()))?),
+        QueryMsg::EpochVotes { epoch } => to_binary(&query_epoch_votes(deps, epoch)?),
+        QueryMsg::UserVotes { addr, epoch } => to_binary(&query_user_votes(deps, env, addr, epoch)?),
+        QueryMsg::LastCastEpoch {} => to_binary(&LastCastEpochResponse { epoch: LAST_CAST_EPOCH.may_load(deps.storage)? }),
+    }
+}
+
+fn query_epoch_votes(deps: Deps, epoch: u64) -> StdResult<EpochVotesResponse> {
+    let (total_weight, votes) = pooled_votes(deps.storage, epoch)?;
+    Ok(EpochVotesResponse { epoch, total_weight, votes })
+}
+
+fn query_user_votes(deps: Deps, env: Env, addr: String, epoch: Option<u64>) -> StdResult<UserVotesResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let epoch = epoch.unwrap_or_else(|| epoch_at(env.block.time.seconds()));
+
+    let res = match USER_VOTES.may_load(deps.storage, (epoch, addr.clone()))? {
+        Some(user_vote) => UserVotesResponse { epoch, power: user_vote.power, votes: user_vote.votes },
+        None => UserVotesResponse { epoch, power: query_voting_power(deps, &addr, epoch)?, votes: vec![] },
+    };
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, Timestamp};
+
+    const ADMIN: &str = "admin";
+
+    fn get_instantiate_msg() -> InstantiateMsg{
+        InstantiateMsg {
+            admin: ADMIN.to_string(),
+            vote_lock: "vote_lock".to_string(),
+            pool_registry: "astro_stake".to_string(),
+            generator_controller: "generator_controller".to_string(),
+        }
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CurrentVotes {}).unwrap();
+        let value: EpochVotesResponse = from_binary(&res).unwrap();
+        assert_eq!(value.epoch, epoch_at(mock_env().block.time.seconds()));
+        assert!(value.votes.is_empty());
+    }
+
+    #[test]
+    fn set_generator_controller() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        let msg = ExecuteMsg::SetGeneratorController { generator_controller: "controller_two".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&res).unwrap();
+        assert_eq!(config.generator_controller, Addr::unchecked("controller_two"));
+    }
+
+    #[test]
+    fn cast_votes_needs_votes() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), get_instantiate_msg()).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::CastVotes {}).unwrap_err();
+        assert!(matches!(err, ContractError::NoVotes { .. }));
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(0);
+        let err = execute(deps.as_mut(), env, mock_info("anyone", &[]), ExecuteMsg::CastVotes {}).unwrap_err();
+        assert!(matches!(err, ContractError::NoVotes { epoch: 0 }));
+    }
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{pool} is not an active pool")]
+    InvalidPool { pool: String },
+
+    #[error("No locked balance to vote with in epoch {epoch}")]
+    NoVotingPower { epoch: u64 },
+
+    #[error("Votes add up to more than 10000 basis points")]
+    VotesTooHigh {},
+
+    #[error("Pool {pool} is voted for more than once")]
+    DuplicatePool { pool: String },
+
+    #[error("No votes to cast for epoch {epoch}")]
+    NoVotes { epoch: u64 },
+
+    #[error("Votes for epoch {epoch} were already cast")]
+    VotesAlreadyCast { epoch: u64 },
+}
@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod tests {
+    use crate::helpers::CwTemplateContract;
+    use crate::msg::{EpochVotesResponse, ExecuteMsg, InstantiateMsg, LastCastEpochResponse, PoolVoteData, QueryMsg};
+    use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
+    use cw20::{Cw20Coin, Cw20ExecuteMsg, MinterResponse};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+    use octane_vote_lock::msg::ReceiveMsg as VoteLockReceiveMsg;
+    use octane_vote_lock::state::{epoch_at, EPOCH_LENGTH};
+
+    pub fn contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_vote_lock() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            octane_vote_lock::contract::execute,
+            octane_vote_lock::contract::instantiate,
+            octane_vote_lock::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    const ADMIN: &str = "admin";
+    const ALICE: &str = "alice";
+    const BOB: &str = "bob";
+    const CAROL: &str = "carol";
+    const POOL_A: &str = "pool_a";
+    const POOL_B: &str = "pool_b";
+    const POOL_SHUT: &str = "pool_shut";
+
+    /// Stands in for the astro_stake pool registry: answers PoolByLpToken for registered LP tokens
+    mod mock_pool_registry {
+        use astro_stake::msg::{PoolResponse, QueryMsg};
+        use astro_stake::state::PoolInfo;
+        use cosmwasm_std::{to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::Map;
+        use serde::{Deserialize, Serialize};
+
+        const POOLS: Map<Addr, bool> = Map::new("pools");
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(rename_all = "snake_case")]
+        pub enum ExecuteMsg {
+            AddPool { lp_token: String, active: bool },
+        }
+
+        fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+            let ExecuteMsg::AddPool { lp_token, active } = msg;
+            POOLS.save(deps.storage, Addr::unchecked(lp_token), &active)?;
+            Ok(Response::new())
+        }
+
+        fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+            match msg {
+                QueryMsg::PoolByLpToken { lp_token } => {
+                    let lp_token = Addr::unchecked(lp_token);
+                    let active = POOLS.load(deps.storage, lp_token.clone())?;
+                    let pool = PoolInfo {
+                        lp_token,
+                        guage: Addr::unchecked("generator"),
+                        rewards: Addr::unchecked("rewards"),
+                        factory: Addr::unchecked("factory"),
+                        active,
+                    };
+                    to_binary(&PoolResponse { pool_id: 0, pool })
+                }
+                _ => Err(StdError::generic_err("not supported")),
+            }
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    /// Stands in for the Astroport generator controller: records the last votes it received
+    mod mock_generator_controller {
+        use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use cw_storage_plus::Item;
+
+        use crate::msg::GeneratorControllerExecuteMsg;
+
+        const VOTES: Item<Vec<(String, u16)>> = Item::new("votes");
+
+        fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: GeneratorControllerExecuteMsg) -> StdResult<Response> {
+            let GeneratorControllerExecuteMsg::Vote { votes } = msg;
+            VOTES.save(deps.storage, &votes)?;
+            Ok(Response::new())
+        }
+
+        fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&VOTES.may_load(deps.storage)?.unwrap_or_default())
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    struct Suite {
+        app: App,
+        voter: CwTemplateContract,
+        vote_lock: Addr,
+        token: Addr,
+        controller: Addr,
+    }
+
+    fn setup() -> Suite {
+        let mut app = App::default();
+
+        let cw20_id = app.store_code(contract_cw20());
+        let msg = cw20_base::msg::InstantiateMsg {
+            name: "oAstro".to_string(),
+            symbol: "OASTRO".to_string(),
+            decimals: 6,
+            initial_balances: [ALICE, BOB, CAROL]
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(1_000),
+                })
+                .collect(),
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        };
+        let token = app
+            .instantiate_contract(cw20_id, Addr::unchecked(ADMIN), &msg, &[], "oastro", None)
+            .unwrap();
+
+        let vote_lock_id = app.store_code(contract_vote_lock());
+        let msg = octane_vote_lock::msg::InstantiateMsg { lock_token: token.to_string(), lock_epochs: 16 };
+        let vote_lock = app
+            .instantiate_contract(vote_lock_id, Addr::unchecked(ADMIN), &msg, &[], "vote_lock", None)
+            .unwrap();
+
+        let controller_id = app.store_code(mock_generator_controller::contract());
+        let controller = app
+            .instantiate_contract(controller_id, Addr::unchecked(ADMIN), &Empty {}, &[], "controller", None)
+            .unwrap();
+
+        let registry_id = app.store_code(mock_pool_registry::contract());
+        let registry = app
+            .instantiate_contract(registry_id, Addr::unchecked(ADMIN), &Empty {}, &[], "astro_stake", None)
+            .unwrap();
+        for (lp_token, active) in [(POOL_A, true), (POOL_B, true), (POOL_SHUT, false)] {
+            let msg = mock_pool_registry::ExecuteMsg::AddPool { lp_token: lp_token.to_string(), active };
+            app.execute_contract(Addr::unchecked(ADMIN), registry.clone(), &msg, &[]).unwrap();
+        }
+
+        let voter_id = app.store_code(contract_template());
+        let msg = InstantiateMsg {
+            admin: ADMIN.to_string(),
+            vote_lock: vote_lock.to_string(),
+            pool_registry: registry.to_string(),
+            generator_controller: controller.to_string(),
+        };
+        let voter = app
+            .instantiate_contract(voter_id, Addr::unchecked(ADMIN), &msg, &[], "voter", None)
+            .unwrap();
+
+        Suite { app, voter: CwTemplateContract(voter), vote_lock, token, controller }
+    }
+
+    impl Suite {
+        fn lock(&mut self, account: &str, amount: u128) {
+            let msg = Cw20ExecuteMsg::Send {
+                contract: self.vote_lock.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&VoteLockReceiveMsg::Lock {}).unwrap(),
+            };
+            self.app
+                .execute_contract(Addr::unchecked(account), self.token.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        fn advance_epochs(&mut self, epochs: u64) {
+            self.app.update_block(|block| block.time = block.time.plus_seconds(epochs * EPOCH_LENGTH));
+        }
+
+        fn execute(&mut self, sender: &str, msg: ExecuteMsg) -> Result<(), String> {
+            self.app
+                .execute(Addr::unchecked(sender), self.voter.call(msg).unwrap())
+                .map(|_| ())
+                .map_err(|err| err.root_cause().to_string())
+        }
+
+        fn vote(&mut self, sender: &str, votes: &[(&str, u16)]) -> Result<(), String> {
+            let votes = votes.iter().map(|(pool, bps)| (pool.to_string(), *bps)).collect();
+            self.execute(sender, ExecuteMsg::Vote { votes })
+        }
+
+        fn current_votes(&self) -> EpochVotesResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(self.voter.addr(), &QueryMsg::CurrentVotes {})
+                .unwrap()
+        }
+
+        fn cast_votes(&self) -> Vec<(String, u16)> {
+            self.app.wrap().query_wasm_smart(&self.controller, &Empty {}).unwrap()
+        }
+    }
+
+    #[test]
+    fn votes_are_pooled_pro_rata_to_lock_weight() {
+        let mut suite = setup();
+        suite.lock(ALICE, 300);
+        suite.lock(BOB, 100);
+        suite.advance_epochs(1);
+
+        suite.vote(ALICE, &[(POOL_A, 10_000)]).unwrap();
+        suite.vote(BOB, &[(POOL_A, 5_000), (POOL_B, 5_000)]).unwrap();
+
+        let votes = suite.current_votes();
+        assert_eq!(votes.total_weight, 400);
+        assert_eq!(votes.votes, vec![
+            PoolVoteData { pool: Addr::unchecked(POOL_A), weight: 350, bps: 8_750 },
+            PoolVoteData { pool: Addr::unchecked(POOL_B), weight: 50, bps: 1_250 },
+        ]);
+
+        // voting again replaces the previous votes
+        suite.vote(BOB, &[(POOL_B, 10_000)]).unwrap();
+        let user_votes = suite.voter.user_votes::<_, _, Empty>(&suite.app, BOB).unwrap();
+        assert_eq!(user_votes.power, 100);
+        assert_eq!(user_votes.votes, vec![(Addr::unchecked(POOL_B), 10_000)]);
+
+        // the previous epoch had no votes
+        assert_eq!(suite.execute(CAROL, ExecuteMsg::CastVotes {}).unwrap_err(), format!("No votes to cast for epoch {}", votes.epoch - 1));
+
+        suite.advance_epochs(1);
+        assert!(suite.current_votes().votes.is_empty());
+        suite.execute(CAROL, ExecuteMsg::CastVotes {}).unwrap();
+        assert_eq!(suite.cast_votes(), vec![(POOL_A.to_string(), 7_500), (POOL_B.to_string(), 2_500)]);
+        let res: LastCastEpochResponse = suite.app.wrap().query_wasm_smart(suite.voter.addr(), &QueryMsg::LastCastEpoch {}).unwrap();
+        assert_eq!(res.epoch, Some(votes.epoch));
+        assert_eq!(
+            suite.execute(CAROL, ExecuteMsg::CastVotes {}).unwrap_err(),
+            format!("Votes for epoch {} were already cast", votes.epoch)
+        );
+    }
+
+    #[test]
+    fn votes_are_validated() {
+        let mut suite = setup();
+        suite.lock(ALICE, 300);
+        suite.advance_epochs(1);
+        let epoch = epoch_at(suite.app.block_info().time.seconds());
+
+        // a lock made during the epoch only counts from the next one
+        suite.lock(CAROL, 100);
+        assert_eq!(suite.vote(CAROL, &[(POOL_A, 10_000)]).unwrap_err(), format!("No locked balance to vote with in epoch {}", epoch));
+
+        assert_eq!(suite.vote(ALICE, &[(POOL_A, 6_000), (POOL_B, 4_001)]).unwrap_err(), "Votes add up to more than 10000 basis points");
+        assert_eq!(suite.vote(ALICE, &[(POOL_A, 3_000), (POOL_A, 3_000)]).unwrap_err(), "Pool pool_a is voted for more than once");
+
+        // only active registry pools can be voted for, anything else would fail the cast
+        assert_eq!(suite.vote(ALICE, &[(POOL_A, 5_000), (ALICE, 1)]).unwrap_err(), "alice is not an active pool");
+        assert_eq!(suite.vote(ALICE, &[(POOL_SHUT, 5_000)]).unwrap_err(), "pool_shut is not an active pool");
+
+        // unvoted basis points are left out of the pooled votes
+        suite.vote(ALICE, &[(POOL_A, 2_500)]).unwrap();
+        let votes = suite.current_votes();
+        assert_eq!(votes.total_weight, 75);
+        assert_eq!(votes.votes, vec![PoolVoteData { pool: Addr::unchecked(POOL_A), weight: 75, bps: 10_000 }]);
+    }
+}
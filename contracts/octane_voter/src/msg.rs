@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub vote_lock: String,
+    pub pool_registry: String,
+    pub generator_controller: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Vote replaces the sender's votes for the current epoch, weights are in basis points
+    // and every pool must be active in the pool registry
+    Vote{votes: Vec<(String, u16)>},
+    // CastVotes sends the previous epoch's votes to the generator controller, anyone may call it
+    CastVotes{},
+    SetGeneratorController{generator_controller: String},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    // CurrentVotes returns the pooled votes of the current epoch as they would be cast
+    CurrentVotes {},
+    EpochVotes {epoch: u64},
+    // UserVotes returns the votes addr submitted for epoch, the current one when not given
+    UserVotes {addr: String, epoch: Option<u64>},
+    // LastCastEpoch returns the last epoch whose votes were sent to the generator controller
+    LastCastEpoch {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolVoteData {
+    pub pool: Addr,
+    pub weight: u128,
+    pub bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochVotesResponse {
+    pub epoch: u64,
+    pub total_weight: u128,
+    pub votes: Vec<PoolVoteData>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserVotesResponse {
+    pub epoch: u64,
+    pub power: u128,
+    pub votes: Vec<(Addr, u16)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LastCastEpochResponse {
+    pub epoch: Option<u64>,
+}
+
+/// Astroport generator controller messages the voter calls
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorControllerExecuteMsg {
+    Vote{votes: Vec<(String, u16)>},
+}
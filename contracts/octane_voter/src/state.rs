@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config{
+    pub vote_lock: Addr,
+    /// astro_stake contract whose active pools can be voted for
+    pub pool_registry: Addr,
+    pub generator_controller: Addr
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserVote{
+    /// Locked balance the votes were weighted with
+    pub power: u128,
+    /// (pool, basis points) pairs, at most 10_000 basis points in total
+    pub votes: Vec<(Addr, u16)>
+}
+
+/// Votes each locker submitted for an epoch, keyed by (epoch, voter)
+pub const USER_VOTES: Map<(u64, Addr), UserVote> = Map::new("user_votes");
+
+/// Lock weight directed to each pool for an epoch, keyed by (epoch, pool)
+pub const POOL_VOTES: Map<(u64, Addr), u128> = Map::new("pool_votes");
+
+/// Weight a voter with `power` gives a pool voted with `bps`. Pool weights are the sum of these.
+pub fn pool_weight(power: u128, bps: u16) -> u128 {
+    Uint128::new(power).multiply_ratio(bps, BPS_DENOMINATOR).u128()
+}
+
+/// Last epoch whose votes were sent to the generator controller
+pub const LAST_CAST_EPOCH: Item<u64> = Item::new("last_cast_epoch");